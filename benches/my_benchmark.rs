@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use jaggedarray::jagged_array::{JaggedArray, JaggedArrayViewTrait};
-use num::{cast::AsPrimitive, traits::{ConstOne, ConstZero, NumAssignOps}, Num};
+use num::{cast::AsPrimitive, traits::{CheckedAdd, ConstOne, ConstZero, NumAssignOps}, Num};
 fn nested_2d_vector_iteration(data: &Vec<Vec<usize>>) -> usize {
     let mut result = 0;
     for i in data {
@@ -84,7 +84,33 @@ fn nested_3d_jagged_array_iteration<T: AsPrimitive<usize> + Num+ConstOne+ConstZe
     result
 }
 
-fn get_2d_jagged_array<T: AsPrimitive<usize> + Num + NumAssignOps + std::cmp::PartialOrd+ConstOne+ConstZero>(
+fn rows_2d_jagged_array_iteration<T: AsPrimitive<usize> + Num+ConstOne+ConstZero>(
+    data: &JaggedArray<usize, Vec<T>, 2>,
+) -> usize {
+    let mut result = 0;
+    for row in data.rows::<1>() {
+        for j in row.as_slice() {
+            result += j;
+        }
+    }
+    result
+}
+
+fn rows_3d_jagged_array_iteration<T: AsPrimitive<usize> + Num+ConstOne+ConstZero>(
+    data: &JaggedArray<usize, Vec<T>, 3>,
+) -> usize {
+    let mut result = 0;
+    for row in data.rows::<2>() {
+        for row in row.rows::<1>() {
+            for k in row.as_slice() {
+                result += k;
+            }
+        }
+    }
+    result
+}
+
+fn get_2d_jagged_array<T: AsPrimitive<usize> + Num + NumAssignOps + CheckedAdd + std::cmp::PartialOrd+ConstOne+ConstZero>(
     iteration: usize,
 ) -> JaggedArray<usize, Vec<T>, 2> where usize: AsPrimitive<T> {
     let mut a = JaggedArray::<usize, Vec<T>, 2>::new();
@@ -97,7 +123,7 @@ fn get_2d_jagged_array<T: AsPrimitive<usize> + Num + NumAssignOps + std::cmp::Pa
     a
 }
 
-fn get_3d_jagged_array<T: AsPrimitive<usize> + Num + NumAssignOps + std::cmp::PartialOrd+ConstOne+ConstZero>(
+fn get_3d_jagged_array<T: AsPrimitive<usize> + Num + NumAssignOps + CheckedAdd + std::cmp::PartialOrd+ConstOne+ConstZero>(
     iteration: usize,
 ) -> JaggedArray<usize, Vec<T>, 3> where usize: AsPrimitive<T> {
     let mut a = JaggedArray::<usize, Vec<T>, 3>::new();
@@ -146,6 +172,9 @@ fn criterion_benchmark(c: &mut Criterion) {
     group.bench_function("2d jagged array iteration 1000(U32)", |b| {
         b.iter(|| nested_2d_jagged_array_iteration(black_box(&a)))
     });
+    group.bench_function("2d jagged array rows iteration 1000(U32)", |b| {
+        b.iter(|| rows_2d_jagged_array_iteration(black_box(&a)))
+    });
     let a = get_2d_nested_vector(10000);
     group.bench_function("2d nested vector iteration 10000", |b| {
         b.iter(|| nested_2d_vector_iteration(black_box(&a)))
@@ -194,6 +223,9 @@ fn criterion_benchmark(c: &mut Criterion) {
     group.bench_function("3d jagged array iteration 100(U32)", |b| {
         b.iter(|| nested_3d_jagged_array_iteration(black_box(&a)))
     });
+    group.bench_function("3d jagged array rows iteration 100(U32)", |b| {
+        b.iter(|| rows_3d_jagged_array_iteration(black_box(&a)))
+    });
     let a = get_3d_nested_vector(500);
     group.bench_function("3d nested vector iteration 500", |b| {
         b.iter(|| nested_3d_vector_iteration(black_box(&a)))
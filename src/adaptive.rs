@@ -0,0 +1,300 @@
+use std::hash::{Hash, Hasher};
+use std::iter::zip;
+use std::ops::Index;
+
+use generic_array::ArrayLength;
+use num::traits::AsPrimitive;
+use typenum::{Const, NonZero, Sub1, ToUInt, Unsigned, B1, U};
+
+use crate::error::JaggedArrayError;
+use crate::jagged_array::{JaggedArray, JaggedArrayViewTrait};
+
+/// A jagged array that picks the narrowest index type its offsets fit in.
+///
+/// The offsets start as `u16` and are transparently re-encoded as `u32` and then `u64`
+/// once a mutation would overflow them, so callers keep a single type regardless of the input size.
+/// Match on the variants to access the underlying [`JaggedArray`] and its views.
+/// Arrays compare and hash by content, regardless of the index type in use.
+///
+/// # Example
+///
+/// ```
+/// use jaggedarray::adaptive::AdaptiveJaggedArray;
+/// let mut data = AdaptiveJaggedArray::<u8, 2>::new();
+/// data.new_row::<0>();
+/// data.extend_last_row_from_slice(&[0; 70000]);
+/// data.push_to_last_row(1);
+/// assert!(data.index_size() == 4);
+/// assert!(data[[0, 70000]] == 1);
+/// ```
+#[derive(Debug, Clone)]
+pub enum AdaptiveJaggedArray<TVal, const N: usize>
+where
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    U16(JaggedArray<TVal, Vec<u16>, N>),
+    U32(JaggedArray<TVal, Vec<u32>, N>),
+    U64(JaggedArray<TVal, Vec<u64>, N>),
+}
+
+macro_rules! dispatch {
+    ($value:expr, $array:ident => $body:expr) => {
+        match $value {
+            AdaptiveJaggedArray::U16($array) => $body,
+            AdaptiveJaggedArray::U32($array) => $body,
+            AdaptiveJaggedArray::U64($array) => $body,
+        }
+    };
+}
+
+impl<TVal, const N: usize> Default for AdaptiveJaggedArray<TVal, N>
+where
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    #[inline]
+    fn default() -> Self {
+        AdaptiveJaggedArray::U16(JaggedArray::new())
+    }
+}
+
+impl<TVal, const N: usize> AdaptiveJaggedArray<TVal, N>
+where
+    U<N>: std::ops::Sub<B1> + ArrayLength,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns the size in bytes of the index type currently in use.
+    #[inline]
+    pub fn index_size(&self) -> usize {
+        match self {
+            AdaptiveJaggedArray::U16(_) => 2,
+            AdaptiveJaggedArray::U32(_) => 4,
+            AdaptiveJaggedArray::U64(_) => 8,
+        }
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        dispatch!(self, array => array.len())
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        dispatch!(self, array => array.is_empty())
+    }
+    #[inline]
+    pub fn buffer_len(&self) -> usize {
+        dispatch!(self, array => array.buffer_len())
+    }
+    #[inline]
+    pub fn get(&self, index: [usize; N]) -> Option<&TVal> {
+        dispatch!(self, array => array.get(index))
+    }
+    /// Removes all values and goes back to `u16` offsets.
+    #[inline]
+    pub fn clear(&mut self) {
+        match self {
+            AdaptiveJaggedArray::U16(array) => array.clear(),
+            _ => *self = Self::new(),
+        }
+    }
+    /// Widens the index type until offsets up to `self.max_offset() + additional` fit in it.
+    fn fit(&mut self, additional: usize) -> Result<(), JaggedArrayError> {
+        let max = dispatch!(&*self, array => array.max_offset())
+            .checked_add(additional)
+            .ok_or(JaggedArrayError::IndexOverflow)?;
+        if matches!(self, AdaptiveJaggedArray::U16(_)) && u16::try_from(max).is_err() {
+            if let AdaptiveJaggedArray::U16(array) = std::mem::take(self) {
+                *self = AdaptiveJaggedArray::U32(array.convert_index());
+            }
+        }
+        if matches!(self, AdaptiveJaggedArray::U32(_)) && u32::try_from(max).is_err() {
+            if let AdaptiveJaggedArray::U32(array) = std::mem::take(self) {
+                *self = AdaptiveJaggedArray::U64(array.convert_index());
+            }
+        }
+        Ok(())
+    }
+    /// Returns how many values can be added to the last row before the index type has to be widened.
+    fn room(&self) -> usize {
+        let limit = match self {
+            AdaptiveJaggedArray::U16(_) => u16::MAX.into(),
+            AdaptiveJaggedArray::U32(_) => usize::try_from(u32::MAX).unwrap_or(usize::MAX),
+            AdaptiveJaggedArray::U64(_) => usize::try_from(u64::MAX).unwrap_or(usize::MAX),
+        };
+        limit - dispatch!(self, array => array.max_offset())
+    }
+    /// See [`JaggedArray::new_row`].
+    #[inline]
+    pub fn new_row<const DIM: usize>(&mut self)
+    where
+        U<N>: std::ops::Sub<U<DIM>>,
+        Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output>: Unsigned + NonZero,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: std::ops::Sub<typenum::B1>,
+        U<DIM>: ArrayLength,
+        Const<DIM>: ToUInt,
+    {
+        self.try_new_row::<DIM>()
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// See [`JaggedArray::try_new_row`].
+    #[inline]
+    pub fn try_new_row<const DIM: usize>(&mut self) -> Result<(), JaggedArrayError>
+    where
+        U<N>: std::ops::Sub<U<DIM>>,
+        Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output>: Unsigned + NonZero,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: std::ops::Sub<typenum::B1>,
+        U<DIM>: ArrayLength,
+        Const<DIM>: ToUInt,
+    {
+        self.fit(1)?;
+        dispatch!(self, array => array.try_new_row::<DIM>())
+    }
+    /// See [`JaggedArray::push_to_last_row`].
+    #[inline]
+    pub fn push_to_last_row(&mut self, val: TVal) {
+        self.try_push_to_last_row(val)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// See [`JaggedArray::try_push_to_last_row`].
+    #[inline]
+    pub fn try_push_to_last_row(&mut self, val: TVal) -> Result<(), JaggedArrayError> {
+        self.fit(1)?;
+        dispatch!(self, array => array.try_push_to_last_row(val))
+    }
+    /// See [`JaggedArray::pop_from_last_row`].
+    #[inline]
+    pub fn pop_from_last_row(&mut self) -> Option<TVal> {
+        dispatch!(self, array => array.pop_from_last_row())
+    }
+    /// See [`JaggedArray::extend_last_row`].
+    #[inline]
+    pub fn extend_last_row(&mut self, values: impl Iterator<Item = TVal>) {
+        self.try_extend_last_row(values)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// See [`JaggedArray::try_extend_last_row`].
+    ///
+    /// Values are added while they fit in the current index type,
+    /// which is only widened once `values` actually yields more than that.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::adaptive::AdaptiveJaggedArray;
+    /// let mut data = AdaptiveJaggedArray::<u32, 2>::new();
+    /// data.new_row::<0>();
+    /// assert!(data.try_extend_last_row((0..100_000).filter(|x| x % 2 == 0)).is_ok());
+    /// assert!(data.index_size() == 2);
+    /// assert!(data.try_extend_last_row(0..50_000).is_ok());
+    /// assert!(data.index_size() == 4);
+    /// assert!(data[[0, 99_999]] == 49_999);
+    /// ```
+    pub fn try_extend_last_row(
+        &mut self,
+        values: impl Iterator<Item = TVal>,
+    ) -> Result<(), JaggedArrayError> {
+        let mut values = values.peekable();
+        loop {
+            let room = self.room();
+            dispatch!(self, array => array.try_extend_last_row(values.by_ref().take(room)))?;
+            if values.peek().is_none() {
+                return Ok(());
+            }
+            self.fit(1)?;
+        }
+    }
+    /// See [`JaggedArray::extend_last_row_from_slice`].
+    #[inline]
+    pub fn extend_last_row_from_slice(&mut self, values: &[TVal])
+    where
+        TVal: Clone,
+    {
+        self.try_extend_last_row_from_slice(values)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// See [`JaggedArray::try_extend_last_row_from_slice`].
+    #[inline]
+    pub fn try_extend_last_row_from_slice(
+        &mut self,
+        values: &[TVal],
+    ) -> Result<(), JaggedArrayError>
+    where
+        TVal: Clone,
+    {
+        self.fit(values.len())?;
+        dispatch!(self, array => array.try_extend_last_row_from_slice(values))
+    }
+}
+
+impl<TVal: PartialEq, const N: usize> PartialEq for AdaptiveJaggedArray<TVal, N>
+where
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    fn eq(&self, other: &Self) -> bool {
+        dispatch!(self, left => dispatch!(other, right => {
+            left.buffer == right.buffer
+                && zip(left.indices.iter(), right.indices.iter()).all(|(left, right)| {
+                    left.iter().map(|&x| AsPrimitive::<u64>::as_(x)).eq(right.iter().map(|&x| AsPrimitive::<u64>::as_(x)))
+                })
+        }))
+    }
+}
+
+impl<TVal: Eq, const N: usize> Eq for AdaptiveJaggedArray<TVal, N>
+where
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+}
+
+impl<TVal: Hash, const N: usize> Hash for AdaptiveJaggedArray<TVal, N>
+where
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        dispatch!(self, array => {
+            for index in array.indices.iter() {
+                index.len().hash(state);
+                index.iter().for_each(|&x| AsPrimitive::<u64>::as_(x).hash(state));
+            }
+            array.buffer.hash(state);
+        })
+    }
+}
+
+impl<TVal, const N: usize> Index<[usize; N]> for AdaptiveJaggedArray<TVal, N>
+where
+    U<N>: std::ops::Sub<B1> + ArrayLength,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Output = TVal;
+    #[inline]
+    fn index(&self, index: [usize; N]) -> &TVal {
+        dispatch!(self, array => &array[index])
+    }
+}
+
+impl<TVal, const N: usize> From<JaggedArray<TVal, Vec<u16>, N>> for AdaptiveJaggedArray<TVal, N>
+where
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    #[inline]
+    fn from(value: JaggedArray<TVal, Vec<u16>, N>) -> Self {
+        AdaptiveJaggedArray::U16(value)
+    }
+}
@@ -0,0 +1,204 @@
+//! Conversions between jagged arrays and nested Arrow list arrays.
+//!
+//! The offsets of every level of a jagged array follow the Arrow layout, so a [`JaggedArray`] with `i32`
+//! (or `i64`) offsets maps to nested [`ListArray`](arrow_array::ListArray)s
+//! (or [`LargeListArray`](arrow_array::LargeListArray)s) of a [`PrimitiveArray`] without copying.
+use std::sync::Arc;
+
+use arrow_array::types::ArrowPrimitiveType;
+use arrow_array::{Array, ArrayRef, GenericListArray, OffsetSizeTrait, PrimitiveArray};
+use arrow_buffer::{ArrowNativeType, OffsetBuffer, ScalarBuffer};
+use arrow_schema::Field;
+use generic_array::ArrayLength;
+use num::traits::{AsPrimitive, CheckedAdd, ConstOne, ConstZero, NumAssignOps};
+use num::Num;
+use typenum::{Const, NonZero, ToUInt, B1, U};
+
+use crate::error::JaggedArrayError;
+use crate::jagged_array::{JaggedArray, JaggedArrayMutView, JaggedArrayOwnedView, JaggedArrayView};
+
+/// Takes the memory of `buffer` if it is not shared, or copies it otherwise.
+fn into_vec<T: ArrowNativeType>(buffer: ScalarBuffer<T>) -> Vec<T> {
+    buffer
+        .into_inner()
+        .into_vec()
+        .unwrap_or_else(|buffer| buffer.typed_data::<T>().to_vec())
+}
+
+/// Nests `levels` of offsets, outermost first, around the values in `buffer`.
+fn to_list<T, O>(
+    levels: impl DoubleEndedIterator<Item = Vec<O>>,
+    buffer: Vec<T::Native>,
+) -> GenericListArray<O>
+where
+    T: ArrowPrimitiveType,
+    O: OffsetSizeTrait,
+{
+    let mut values: ArrayRef = Arc::new(PrimitiveArray::<T>::new(buffer.into(), None));
+    for offsets in levels.rev() {
+        let field = Arc::new(Field::new("item", values.data_type().clone(), false));
+        // SAFETY: the offsets of a jagged array start at zero and never decrease
+        let offsets = unsafe { OffsetBuffer::new_unchecked(offsets.into()) };
+        values = Arc::new(GenericListArray::new(field, offsets, values, None));
+    }
+    values
+        .as_any()
+        .downcast_ref::<GenericListArray<O>>()
+        .unwrap()
+        .clone()
+}
+
+impl<TVal, O, const N: usize> JaggedArray<TVal, Vec<O>, N>
+where
+    TVal: ArrowNativeType,
+    O: OffsetSizeTrait
+        + AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: AsPrimitive<O>,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength + NonZero,
+    Const<N>: ToUInt,
+{
+    /// Converts the array to `N - 1` nested Arrow list arrays of a primitive array of `T`,
+    /// moving the offsets and the values without copying them.
+    ///
+    /// `i32` offsets produce [`ListArray`](arrow_array::ListArray)s
+    /// and `i64` offsets produce [`LargeListArray`](arrow_array::LargeListArray)s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use arrow_array::{cast::AsArray, types::Int64Type, Array};
+    /// use jaggedarray::jagged;
+    /// let data = jagged![i32; [1i64, 2], [], [3]];
+    /// let list = data.into_arrow::<Int64Type>();
+    /// assert!(list.value_offsets() == [0, 2, 2, 3]);
+    /// assert!(list.values().as_primitive::<Int64Type>().values() == &[1, 2, 3]);
+    /// ```
+    pub fn into_arrow<T>(self) -> GenericListArray<O>
+    where
+        T: ArrowPrimitiveType<Native = TVal>,
+    {
+        let (indices, buffer) = self.into_raw_parts();
+        to_list::<T, O>(indices.into_iter(), buffer)
+    }
+    /// Converts `N - 1` nested Arrow list arrays of a primitive array of `T` to a jagged array.
+    ///
+    /// The offsets and the values are moved without copying them if they are not shared with other arrays,
+    /// do not contain unreferenced values and the offsets start from zero. They are copied otherwise.
+    ///
+    /// Returns an error if a nested array is not a list array of the same offset type or a primitive array of `T`,
+    /// or if any of them contains nulls.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use arrow_array::{types::Int32Type, ListArray};
+    /// use jaggedarray::{jagged, JaggedArray};
+    /// let list = ListArray::from_iter_primitive::<Int32Type, _, _>([
+    ///     Some([Some(1), Some(2)]),
+    ///     Some([Some(3), Some(4)]),
+    /// ]);
+    /// let data = JaggedArray::<i32, Vec<i32>, 2>::try_from_arrow::<Int32Type>(list.slice(1, 1));
+    /// assert!(data.unwrap() == jagged![i32; [3, 4]]);
+    /// ```
+    pub fn try_from_arrow<T>(array: GenericListArray<O>) -> Result<Self, JaggedArrayError>
+    where
+        T: ArrowPrimitiveType<Native = TVal>,
+    {
+        let mut indices = Vec::with_capacity(N - 1);
+        let mut array: ArrayRef = Arc::new(array);
+        for _ in 0..N - 1 {
+            let list = array
+                .as_any()
+                .downcast_ref::<GenericListArray<O>>()
+                .ok_or(JaggedArrayError::InvalidFormat("unexpected data type"))?
+                .clone();
+            drop(array);
+            if list.null_count() > 0 {
+                return Err(JaggedArrayError::NullValues);
+            }
+            let (_, offsets, values, _) = list.into_parts();
+            let first = offsets[0];
+            let last = offsets[offsets.len() - 1];
+            if first == O::ZERO && last.as_() == values.len() {
+                indices.push(into_vec(offsets.into_inner()));
+                array = values;
+            } else {
+                indices.push(offsets.iter().map(|&offset| offset - first).collect());
+                array = values.slice(first.as_(), (last - first).as_());
+            }
+        }
+        let values = array
+            .as_any()
+            .downcast_ref::<PrimitiveArray<T>>()
+            .ok_or(JaggedArrayError::InvalidFormat("unexpected data type"))?
+            .clone();
+        drop(array);
+        if values.null_count() > 0 {
+            return Err(JaggedArrayError::NullValues);
+        }
+        let (_, buffer, _) = values.into_parts();
+        Ok(JaggedArray {
+            indices: indices.into_iter().collect(),
+            buffer: into_vec(buffer),
+        })
+    }
+}
+
+impl<TVal, O, const N: usize> JaggedArrayOwnedView<TVal, O, N>
+where
+    TVal: ArrowNativeType,
+    O: OffsetSizeTrait + AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength + NonZero,
+    Const<N>: ToUInt,
+{
+    /// See [`JaggedArray::into_arrow`].
+    pub fn into_arrow<T>(self) -> GenericListArray<O>
+    where
+        T: ArrowPrimitiveType<Native = TVal>,
+    {
+        to_list::<T, O>(
+            self.indices.into_iter().map(Vec::from),
+            Vec::from(self.buffer),
+        )
+    }
+}
+
+macro_rules! impl_to_arrow {
+    ($typ:ident< $( $gen:tt ),+>) => {
+        impl<$( $gen ),+, O, const N: usize> $typ<$($gen),+, O, N>
+        where
+            TVal: ArrowNativeType,
+            O: OffsetSizeTrait + AsPrimitive<usize> + Num,
+            U<N>: std::ops::Sub<B1>,
+            <U<N> as std::ops::Sub<B1>>::Output: ArrayLength + NonZero,
+            Const<N>: ToUInt,
+        {
+            /// Copies the view into `N - 1` nested Arrow list arrays of a primitive array of `T`,
+            /// since the borrowed memory cannot be shared with Arrow.
+            ///
+            /// See [`JaggedArray::into_arrow`].
+            pub fn to_arrow<T>(&self) -> GenericListArray<O>
+            where
+                T: ArrowPrimitiveType<Native = TVal>,
+            {
+                to_list::<T, O>(
+                    self.indices
+                        .iter()
+                        .map(|index| index.iter().map(|&x| x - index[0]).collect()),
+                    self.buffer.to_vec(),
+                )
+            }
+        }
+    };
+}
+
+impl_to_arrow!(JaggedArrayView<'a, TVal>);
+impl_to_arrow!(JaggedArrayMutView<'a, TVal>);
@@ -0,0 +1,216 @@
+//! A binary file format that can be read back as a [`JaggedArrayView`] without copying.
+//!
+//! # Layout
+//!
+//! Every section starts at a multiple of [`ALIGNMENT`] bytes from the start of the file
+//! and is padded with zeros up to the next one. All integers are stored in the byte order of the writer.
+//!
+//! | Section | Content |
+//! |---------|---------|
+//! | Header  | The magic bytes `b"JAGARRAY"`, the format version(`u8`), the byte order(`u8`, 1 for little endian and 2 for big endian), the index width in bytes(`u8`), the value type tag(`u8`), the value size in bytes(`u32`), `N`(`u32`), the index type tag(`u8`) and 11 reserved bytes |
+//! | Lengths | The length of every level of offsets followed by the length of the buffer, all as `u64` |
+//! | Offsets | One section per level of offsets, outermost first |
+//! | Buffer  | The values |
+use std::io::{self, Write};
+use std::mem::{align_of, size_of};
+
+use bytemuck::Pod;
+use generic_array::{ArrayLength, GenericArray};
+use num::traits::AsPrimitive;
+use num::Num;
+use typenum::{Const, ToUInt, B1, U};
+
+use crate::error::JaggedArrayError;
+use crate::jagged_array::{JaggedArray, JaggedArrayView};
+use crate::vec_like::VecLike;
+
+/// The alignment of every section in bytes.
+pub const ALIGNMENT: usize = 16;
+const MAGIC: &[u8; 8] = b"JAGARRAY";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 32;
+const BYTE_ORDER: u8 = if cfg!(target_endian = "little") { 1 } else { 2 };
+
+/// A plain-old-data value or index type that can be stored in the binary format.
+///
+/// `TAG` identifies the type in the header. The tags from 128 up are free for user-defined types.
+pub trait BinaryValue: Pod {
+    const TAG: u8;
+}
+
+macro_rules! impl_binary_value {
+    ($($typ:ty => $tag:expr),+) => {
+        $(
+            impl BinaryValue for $typ {
+                const TAG: u8 = $tag;
+            }
+        )+
+    };
+}
+
+impl_binary_value!(u8 => 1, u16 => 2, u32 => 3, u64 => 4, u128 => 5);
+impl_binary_value!(i8 => 6, i16 => 7, i32 => 8, i64 => 9, i128 => 10);
+impl_binary_value!(f32 => 11, f64 => 12);
+#[cfg(target_pointer_width = "32")]
+impl_binary_value!(usize => 3, isize => 8);
+#[cfg(target_pointer_width = "64")]
+impl_binary_value!(usize => 4, isize => 9);
+
+fn write_section(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    writer.write_all(bytes)?;
+    let padding = bytes.len().next_multiple_of(ALIGNMENT) - bytes.len();
+    writer.write_all(&[0; ALIGNMENT][..padding])
+}
+
+impl<TVal, TBuffer: VecLike, const N: usize> JaggedArray<TVal, TBuffer, N>
+where
+    TVal: BinaryValue,
+    TBuffer::TI: BinaryValue + AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    /// Writes the array in the [binary format](crate::binary),
+    /// which can be read back with [`JaggedArrayView::from_bytes`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, JaggedArrayView};
+    /// let data = jagged![u32; [1u16, 2], [3]];
+    /// let mut bytes = Vec::new();
+    /// data.write_to(&mut bytes).unwrap();
+    /// // The reader needs the bytes to be aligned, like a memory-mapped file is.
+    /// let mut storage = vec![0u128; bytes.len() / 16];
+    /// bytemuck::cast_slice_mut(&mut storage).copy_from_slice(&bytes);
+    /// let view = JaggedArrayView::<u16, u32, 2>::from_bytes(bytemuck::cast_slice(&storage)).unwrap();
+    /// assert!(view[[1, 0]] == 3);
+    /// ```
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&[
+            VERSION,
+            BYTE_ORDER,
+            size_of::<TBuffer::TI>() as u8,
+            TVal::TAG,
+        ]);
+        header.extend_from_slice(&(size_of::<TVal>() as u32).to_ne_bytes());
+        header.extend_from_slice(&(N as u32).to_ne_bytes());
+        header.push(TBuffer::TI::TAG);
+        header.resize(HEADER_LEN, 0);
+        write_section(&mut writer, &header)?;
+        let lengths = self
+            .indices
+            .iter()
+            .map(|index| index.len() as u64)
+            .chain(std::iter::once(self.buffer.len() as u64))
+            .collect::<Vec<_>>();
+        write_section(&mut writer, bytemuck::cast_slice(&lengths))?;
+        for index in self.indices.iter() {
+            write_section(&mut writer, bytemuck::cast_slice(&index[..]))?;
+        }
+        write_section(&mut writer, bytemuck::cast_slice(&self.buffer))
+    }
+}
+
+/// Splits aligned sections off the front of a file.
+struct Sections<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Sections<'a> {
+    fn next(&mut self, len: usize) -> Result<&'a [u8], JaggedArrayError> {
+        if len > self.bytes.len() {
+            return Err(JaggedArrayError::InvalidFormat("file is truncated"));
+        }
+        let (section, remaining) = self.bytes.split_at(len);
+        let padding = len.next_multiple_of(ALIGNMENT) - len;
+        self.bytes = remaining
+            .get(padding..)
+            .ok_or(JaggedArrayError::InvalidFormat("file is truncated"))?;
+        Ok(section)
+    }
+
+    fn next_slice<T: Pod>(&mut self, len: u64) -> Result<&'a [T], JaggedArrayError> {
+        let len = usize::try_from(len)
+            .ok()
+            .and_then(|len| len.checked_mul(size_of::<T>()))
+            .ok_or(JaggedArrayError::InvalidFormat("file is truncated"))?;
+        bytemuck::try_cast_slice(self.next(len)?)
+            .map_err(|_| JaggedArrayError::InvalidFormat("section is misaligned"))
+    }
+}
+
+impl<'a, TVal, TNum, const N: usize> JaggedArrayView<'a, TVal, TNum, N>
+where
+    TVal: BinaryValue,
+    TNum: BinaryValue + AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    /// Creates a view that borrows the offsets and values of an array
+    /// written by [`JaggedArray::write_to`] directly from `bytes`.
+    ///
+    /// `bytes` must start at a multiple of [`ALIGNMENT`] bytes, which holds for memory-mapped files.
+    /// The header must match the index type, the value type and the dimension of the view,
+    /// and the offsets are checked like [`JaggedArrayView::from_raw_parts`] does.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jaggedarray::{JaggedArrayView, JaggedArrayViewTrait};
+    /// let file = std::fs::File::open("tokens.bin")?;
+    /// // SAFETY: the file must not be modified while it is mapped
+    /// let bytes = unsafe { memmap2::Mmap::map(&file)? };
+    /// let sentences = JaggedArrayView::<u32, u64, 2>::from_bytes(&bytes)?;
+    /// println!("{} sentences", sentences.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, JaggedArrayError> {
+        if !(bytes.as_ptr() as usize).is_multiple_of(ALIGNMENT) {
+            return Err(JaggedArrayError::InvalidFormat("file is misaligned"));
+        }
+        if align_of::<TVal>() > ALIGNMENT || align_of::<TNum>() > ALIGNMENT {
+            return Err(JaggedArrayError::InvalidFormat(
+                "type alignment is too large",
+            ));
+        }
+        let mut sections = Sections { bytes };
+        let header = sections.next(HEADER_LEN)?;
+        let read_u32 = |at: usize| u32::from_ne_bytes(header[at..at + 4].try_into().unwrap());
+        if &header[..8] != MAGIC {
+            return Err(JaggedArrayError::InvalidFormat("not a jagged array file"));
+        }
+        if header[8] != VERSION {
+            return Err(JaggedArrayError::InvalidFormat("unsupported version"));
+        }
+        if header[9] != BYTE_ORDER {
+            return Err(JaggedArrayError::InvalidFormat("byte order mismatch"));
+        }
+        if usize::from(header[10]) != size_of::<TNum>() {
+            return Err(JaggedArrayError::InvalidFormat("index width mismatch"));
+        }
+        if header[20] != TNum::TAG {
+            return Err(JaggedArrayError::InvalidFormat("index type mismatch"));
+        }
+        if header[11] != TVal::TAG || read_u32(12) as usize != size_of::<TVal>() {
+            return Err(JaggedArrayError::InvalidFormat("value type mismatch"));
+        }
+        if read_u32(16) as usize != N {
+            return Err(JaggedArrayError::InvalidFormat("dimension mismatch"));
+        }
+        let lengths = sections.next_slice::<u64>(N as u64)?;
+        let mut indices = GenericArray::uninit();
+        for (dst, &len) in indices.iter_mut().zip(lengths) {
+            dst.write(sections.next_slice::<TNum>(len)?);
+        }
+        // SAFETY: `lengths` holds `N` lengths, so every level has been initialized above
+        let indices = unsafe { GenericArray::assume_init(indices) };
+        let buffer = sections.next_slice::<TVal>(lengths[N - 1])?;
+        Self::from_raw_parts(indices, buffer)
+    }
+}
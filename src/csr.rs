@@ -0,0 +1,247 @@
+use num::traits::{AsPrimitive, CheckedAdd, ConstOne, ConstZero, NumAssignOps};
+use num::Num;
+
+use crate::error::JaggedArrayError;
+use crate::jagged_array::{to_index, JaggedArray, JaggedArrayView};
+
+/// A compressed sparse row matrix over a two-dimensional jagged array of `(column, value)` pairs.
+///
+/// Every row of the array is a row of the matrix and holds its non-zero entries.
+/// The entries of a row do not have to be sorted by column.
+///
+/// # Example
+///
+/// ```
+/// use jaggedarray::csr::CsrView;
+/// use jaggedarray::{jagged, JaggedArrayViewTrait};
+/// let data = jagged![u32; [(0u32, 1.0), (2, 2.0)], [], [(1, 3.0)]];
+/// let matrix = CsrView::new(data.view::<0, 2>([]), 3).unwrap();
+/// assert!(matrix.shape() == (3, 3));
+/// assert!(matrix.mul_vec(&[1.0, 2.0, 3.0]) == [7.0, 0.0, 6.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsrView<'a, TIdx, TVal, TNum>
+where
+    TNum: AsPrimitive<usize> + Num,
+{
+    view: JaggedArrayView<'a, (TIdx, TVal), TNum, 2>,
+    cols: usize,
+}
+
+impl<'a, TIdx, TVal, TNum> CsrView<'a, TIdx, TVal, TNum>
+where
+    TIdx: AsPrimitive<usize>,
+    TNum: AsPrimitive<usize> + Num + NumAssignOps + CheckedAdd + PartialOrd + ConstOne + ConstZero,
+    usize: AsPrimitive<TNum>,
+{
+    /// Creates a matrix with `cols` columns from the rows of `view`.
+    ///
+    /// Returns [`JaggedArrayError::OutOfBounds`] if an entry has a column index not less than `cols`.
+    pub fn new(
+        view: JaggedArrayView<'a, (TIdx, TVal), TNum, 2>,
+        cols: usize,
+    ) -> Result<Self, JaggedArrayError> {
+        if view.buffer.iter().any(|(col, _)| col.as_() >= cols) {
+            return Err(JaggedArrayError::OutOfBounds);
+        }
+        Ok(Self { view, cols })
+    }
+    #[inline]
+    pub fn view(&self) -> &JaggedArrayView<'a, (TIdx, TVal), TNum, 2> {
+        &self.view
+    }
+    /// Returns the number of rows and columns of the matrix.
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) {
+        (self.view.indices[0].len() - 1, self.cols)
+    }
+    /// Returns the number of stored entries.
+    #[inline]
+    pub fn nnz(&self) -> usize {
+        self.view.buffer.len()
+    }
+    /// Returns the range of the stored entries of each row.
+    fn row_ranges(&self) -> impl Iterator<Item = std::ops::Range<usize>> + '_ {
+        let offsets = self.view.indices[0];
+        let base = offsets[0].as_();
+        offsets
+            .windows(2)
+            .map(move |window| window[0].as_() - base..window[1].as_() - base)
+    }
+    /// Multiplies the matrix by the dense vector `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `x` is not the number of columns.
+    pub fn mul_vec(&self, x: &[TVal]) -> Vec<TVal>
+    where
+        TVal: Copy + Num,
+    {
+        assert!(
+            x.len() == self.cols,
+            "vector length {} does not match the {} columns of the matrix",
+            x.len(),
+            self.cols
+        );
+        self.row_ranges()
+            .map(|range| {
+                self.view.buffer[range]
+                    .iter()
+                    .fold(TVal::zero(), |sum, &(col, value)| {
+                        sum + value * x[col.as_()]
+                    })
+            })
+            .collect()
+    }
+    /// See [`CsrView::try_to_csc`].
+    #[inline]
+    pub fn to_csc(&self) -> JaggedArray<(TIdx, TVal), Vec<TNum>, 2>
+    where
+        TVal: Clone,
+        usize: AsPrimitive<TIdx>,
+    {
+        self.try_to_csc().unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Returns the matrix in compressed sparse column layout, as an array that holds
+    /// the `(row, value)` pairs of every column. This is also the CSR layout of the transposed matrix.
+    ///
+    /// The entries of every column are sorted by row.
+    /// Returns [`JaggedArrayError::IndexOverflow`] if a row index does not fit in `TIdx`
+    /// or the offsets do not fit in `TNum`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::csr::CsrView;
+    /// use jaggedarray::{jagged, JaggedArrayViewTrait};
+    /// let data = jagged![u32; [(2u32, 1), (0, 2)], [(2, 3)]];
+    /// let matrix = CsrView::new(data.view::<0, 2>([]), 4).unwrap();
+    /// let csc = matrix.try_to_csc().unwrap();
+    /// assert!(csc == jagged![u32; [(0u32, 2)], [], [(0, 1), (1, 3)], []]);
+    /// ```
+    pub fn try_to_csc(&self) -> Result<JaggedArray<(TIdx, TVal), Vec<TNum>, 2>, JaggedArrayError>
+    where
+        TVal: Clone,
+        usize: AsPrimitive<TIdx>,
+    {
+        let (rows, cols) = self.shape();
+        if let Some(last) = rows.checked_sub(1) {
+            to_index::<TIdx>(last)?;
+        }
+        let mut starts = vec![0usize; cols + 1];
+        for (col, _) in self.view.buffer {
+            starts[col.as_() + 1] += 1;
+        }
+        for col in 0..cols {
+            starts[col + 1] += starts[col];
+        }
+        let offsets = starts
+            .iter()
+            .map(|&offset| to_index(offset))
+            .collect::<Result<Vec<TNum>, _>>()?;
+        // Scatter the source positions column by column, visiting rows in order keeps every column sorted.
+        let mut positions = vec![(0usize, 0usize); self.nnz()];
+        for (row, range) in self.row_ranges().enumerate() {
+            for source in range {
+                let next = &mut starts[self.view.buffer[source].0.as_()];
+                positions[*next] = (row, source);
+                *next += 1;
+            }
+        }
+        let mut buffer = Vec::new();
+        buffer.try_reserve(positions.len())?;
+        buffer.extend(
+            positions
+                .into_iter()
+                .map(|(row, source)| (row.as_(), self.view.buffer[source].1.clone())),
+        );
+        JaggedArray::from_raw_parts([offsets].into(), buffer)
+    }
+}
+
+#[cfg(feature = "sprs")]
+impl<TIdx, TVal, TNum> CsrView<'_, TIdx, TVal, TNum>
+where
+    TIdx: AsPrimitive<usize> + sprs::SpIndex,
+    TNum: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + PartialOrd
+        + ConstOne
+        + ConstZero
+        + sprs::SpIndex,
+    usize: AsPrimitive<TNum>,
+{
+    /// Copies the matrix into a [`sprs::CsMatI`] in CSR layout.
+    ///
+    /// The entries of every row are sorted by column on the way.
+    /// Returns [`JaggedArrayError::InvalidFormat`] if a row holds the same column twice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::csr::CsrView;
+    /// use jaggedarray::{jagged, JaggedArrayViewTrait};
+    /// let data = jagged![usize; [(1usize, 1.0), (0, 2.0)], [(1, 3.0)]];
+    /// let matrix = CsrView::new(data.view::<0, 2>([]), 2).unwrap().to_sprs().unwrap();
+    /// assert!(matrix.get(0, 0) == Some(&2.0));
+    /// assert!(matrix.get(1, 0).is_none());
+    /// ```
+    pub fn to_sprs(&self) -> Result<sprs::CsMatI<TVal, TIdx, TNum>, JaggedArrayError>
+    where
+        TVal: Clone,
+    {
+        let offsets = self.view.indices[0];
+        let indptr = offsets.iter().map(|&x| x - offsets[0]).collect();
+        let (indices, data) = self.view.buffer.iter().cloned().unzip();
+        sprs::CsMatI::new_from_unsorted(self.shape(), indptr, indices, data).map_err(
+            |(_, _, _, error)| match error {
+                sprs::errors::StructureError::Unsorted(reason)
+                | sprs::errors::StructureError::SizeMismatch(reason)
+                | sprs::errors::StructureError::OutOfRange(reason) => {
+                    JaggedArrayError::InvalidFormat(reason)
+                }
+            },
+        )
+    }
+}
+
+#[cfg(feature = "sprs")]
+impl<TIdx, TVal, TNum> From<sprs::CsMatI<TVal, TIdx, TNum>>
+    for JaggedArray<(TIdx, TVal), Vec<TNum>, 2>
+where
+    TIdx: sprs::SpIndex,
+    TVal: Clone + Default,
+    TNum: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + PartialOrd
+        + ConstOne
+        + ConstZero
+        + sprs::SpIndex,
+    usize: AsPrimitive<TNum>,
+{
+    /// Takes the rows of a sparse matrix, converting it to CSR layout first if it is in CSC layout.
+    /// The number of columns is not kept.
+    fn from(matrix: sprs::CsMatI<TVal, TIdx, TNum>) -> Self {
+        let matrix = if matrix.is_csr() {
+            matrix
+        } else {
+            matrix.to_csr()
+        };
+        let (indptr, indices, data) = matrix.into_raw_storage();
+        // The offsets of a sliced matrix may not start from zero.
+        let (start, end) = (indptr[0].as_(), indptr[indptr.len() - 1].as_());
+        let offsets = indptr.iter().map(|&x| x - indptr[0]).collect::<Vec<_>>();
+        let buffer = indices
+            .into_iter()
+            .zip(data)
+            .take(end)
+            .skip(start)
+            .collect();
+        JaggedArray::from_raw_parts([offsets].into(), buffer)
+            .expect("sprs matrices have valid offsets")
+    }
+}
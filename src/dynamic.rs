@@ -0,0 +1,492 @@
+use std::iter::zip;
+
+use generic_array::{ArrayLength, GenericArray};
+use num::traits::{AsPrimitive, CheckedAdd, ConstOne, ConstZero, NumAssignOps};
+use num::Num;
+use typenum::{Const, ToUInt, B1, U};
+
+use crate::error::JaggedArrayError;
+use crate::jagged_array::{to_index, validate_offsets, JaggedArray, JaggedArrayView};
+use crate::vec_like::VecLike;
+
+/// A jagged array whose number of dimensions is only known at runtime.
+///
+/// It has the same layout as [`JaggedArray`], one level of offsets per dimension but the last,
+/// and converts to and from it once the number of dimensions is known.
+///
+/// # Example
+///
+/// ```
+/// use jaggedarray::{jagged, JaggedArray, JaggedArrayDyn};
+/// let dim = "3".parse().unwrap();
+/// let mut data = JaggedArrayDyn::<i32, Vec<u16>>::new(dim);
+/// data.new_row(0);
+/// data.new_row(1);
+/// data.extend_last_row_from_slice(&[1, 2]);
+/// data.new_row(1);
+/// data.push_to_last_row(3);
+/// assert!(data.get(&[0, 1, 0]) == Some(&3));
+/// assert!(data.view(&[0]).len() == 2);
+/// let data = JaggedArray::<i32, Vec<u16>, 3>::try_from(data).unwrap();
+/// assert!(data == jagged![u16; [[1, 2], [3]]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JaggedArrayDyn<TVal, TBuffer: VecLike> {
+    indices: Vec<TBuffer>,
+    buffer: Vec<TVal>,
+}
+
+/// A view into a [`JaggedArrayDyn`], or into any jagged array once converted from its view.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JaggedArrayDynView<'a, TVal, TNum> {
+    // Every level is a window into the offsets of the viewed array, as in `JaggedArrayView`.
+    indices: Vec<&'a [TNum]>,
+    buffer: &'a [TVal],
+}
+
+/// Returns the range of the row at `rows` in the level after the last one indexed,
+/// or in the buffer if every level is indexed.
+fn locate<'a, TNum: AsPrimitive<usize>>(
+    levels: impl Iterator<Item = &'a [TNum]>,
+    len: usize,
+    rows: &[usize],
+) -> Option<(usize, usize)> {
+    let mut range = (0, len);
+    for (&row, level) in zip(rows, levels) {
+        if row >= range.1 - range.0 {
+            return None;
+        }
+        let base = level[0].as_();
+        let position = range.0 + row;
+        range = (
+            level[position].as_() - base,
+            level[position + 1].as_() - base,
+        );
+    }
+    Some(range)
+}
+
+/// Creates a view of the row at `index` from the levels and the buffer of an array.
+fn view_levels<'a, TVal, TNum: AsPrimitive<usize>>(
+    levels: impl ExactSizeIterator<Item = &'a [TNum]> + Clone,
+    buffer: &'a [TVal],
+    index: &[usize],
+) -> Result<JaggedArrayDynView<'a, TVal, TNum>, JaggedArrayError> {
+    if index.len() > levels.len() {
+        return Err(JaggedArrayError::OutOfBounds);
+    }
+    let len = levels
+        .clone()
+        .next()
+        .map_or(buffer.len(), |level| level.len() - 1);
+    let (mut start, mut end) =
+        locate(levels.clone(), len, index).ok_or(JaggedArrayError::OutOfBounds)?;
+    let mut indices = Vec::with_capacity(levels.len() - index.len());
+    for level in levels.skip(index.len()) {
+        let window = &level[start..=end];
+        let base = level[0].as_();
+        (start, end) = (
+            window[0].as_() - base,
+            window[window.len() - 1].as_() - base,
+        );
+        indices.push(window);
+    }
+    Ok(JaggedArrayDynView {
+        indices,
+        buffer: &buffer[start..end],
+    })
+}
+
+impl<TVal, TBuffer: VecLike> JaggedArrayDyn<TVal, TBuffer>
+where
+    TBuffer::TI:
+        AsPrimitive<usize> + Num + NumAssignOps + CheckedAdd + PartialOrd + ConstOne + ConstZero,
+    usize: AsPrimitive<TBuffer::TI>,
+{
+    /// Creates an empty array with `dim` dimensions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim` is zero.
+    pub fn new(dim: usize) -> Self {
+        assert!(dim > 0, "jagged arrays have at least one dimension");
+        Self {
+            indices: (1..dim)
+                .map(|_| {
+                    let mut index = TBuffer::default();
+                    index.push(TBuffer::TI::ZERO);
+                    index
+                })
+                .collect(),
+            buffer: Vec::new(),
+        }
+    }
+    /// Creates an array from its offsets and values, with one dimension more than the levels of offsets.
+    /// See [`JaggedArray::from_raw_parts`] for the layout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::JaggedArrayDyn;
+    /// let data = JaggedArrayDyn::from_raw_parts(vec![vec![0u16, 2, 3]], vec![1, 2, 3]).unwrap();
+    /// assert!(data.dim() == 2 && data.get(&[1, 0]) == Some(&3));
+    /// assert!(JaggedArrayDyn::from_raw_parts(vec![vec![0u16, 2, 4]], vec![1, 2, 3]).is_err());
+    /// ```
+    pub fn from_raw_parts(
+        indices: Vec<TBuffer>,
+        buffer: Vec<TVal>,
+    ) -> Result<Self, JaggedArrayError> {
+        let result = Self { indices, buffer };
+        result.validate()?;
+        Ok(result)
+    }
+    /// Decomposes the array into its offsets and values.
+    #[inline]
+    pub fn into_raw_parts(self) -> (Vec<TBuffer>, Vec<TVal>) {
+        (self.indices, self.buffer)
+    }
+    /// Checks that the offsets describe a valid array. See [`JaggedArray::validate`].
+    pub fn validate(&self) -> Result<(), JaggedArrayError> {
+        validate_offsets(
+            self.indices.iter().map(|index| &index[..]),
+            self.buffer.len(),
+            true,
+        )
+    }
+    /// Returns the number of dimensions.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.indices.len() + 1
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self.indices.first() {
+            Some(index) => index.len() - 1,
+            None => self.buffer.len(),
+        }
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+    #[inline]
+    pub fn buffer_len(&self) -> usize {
+        self.buffer.len()
+    }
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        for index in self.indices.iter_mut() {
+            index.clear();
+            index.push(TBuffer::TI::ZERO);
+        }
+    }
+    /// Returns the value at `index`, or `None` if it is out of bounds
+    /// or does not have one entry per dimension.
+    #[inline]
+    pub fn get(&self, index: &[usize]) -> Option<&TVal> {
+        let (&last, rows) = index.split_last()?;
+        if rows.len() != self.indices.len() {
+            return None;
+        }
+        let (start, end) = locate(
+            self.indices.iter().map(|index| &index[..]),
+            self.len(),
+            rows,
+        )?;
+        self.buffer[start..end].get(last)
+    }
+    #[inline]
+    pub fn as_view(&self) -> JaggedArrayDynView<'_, TVal, TBuffer::TI> {
+        JaggedArrayDynView {
+            indices: self.indices.iter().map(|index| &index[..]).collect(),
+            buffer: &self.buffer,
+        }
+    }
+    /// See [`JaggedArrayDyn::try_view`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn view(&self, index: &[usize]) -> JaggedArrayDynView<'_, TVal, TBuffer::TI> {
+        self.try_view(index)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Returns a view of the row at `index`, which has `self.dim() - index.len()` dimensions.
+    ///
+    /// Returns [`JaggedArrayError::OutOfBounds`] if a row does not exist
+    /// or `index` does not leave at least one dimension.
+    #[inline]
+    pub fn try_view(
+        &self,
+        index: &[usize],
+    ) -> Result<JaggedArrayDynView<'_, TVal, TBuffer::TI>, JaggedArrayError> {
+        view_levels(
+            self.indices.iter().map(|index| &index[..]),
+            &self.buffer,
+            index,
+        )
+    }
+    /// See [`JaggedArrayDyn::try_new_row`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim` is not less than `self.dim() - 1` or the new offsets do not fit in the index type.
+    #[inline]
+    pub fn new_row(&mut self, dim: usize) {
+        self.try_new_row(dim)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Appends an empty row of dimension `dim` to the last row of the dimension before it.
+    /// See [`JaggedArray::try_new_row`].
+    ///
+    /// Returns [`JaggedArrayError::OutOfBounds`] if `dim` is not less than `self.dim() - 1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{JaggedArrayDyn, JaggedArrayError};
+    /// let mut data = JaggedArrayDyn::<i32, Vec<u16>>::new(2);
+    /// assert!(data.try_new_row(0).is_ok());
+    /// assert!(data.try_new_row(1) == Err(JaggedArrayError::OutOfBounds));
+    /// ```
+    pub fn try_new_row(&mut self, dim: usize) -> Result<(), JaggedArrayError> {
+        if dim >= self.indices.len() {
+            return Err(JaggedArrayError::OutOfBounds);
+        }
+        let parent = match dim.checked_sub(1) {
+            Some(level) => Some(to_index(self.indices[level].last().unwrap().as_() + 1)?),
+            None => None,
+        };
+        let index = &mut self.indices[dim];
+        let new_val = *index.last().unwrap();
+        index.try_push(new_val)?;
+        if let Some(parent) = parent {
+            *self.indices[dim - 1].last_mut().unwrap() = parent;
+        }
+        Ok(())
+    }
+    /// # Panics
+    ///
+    /// Panics if the new offsets do not fit in the index type.
+    #[inline]
+    pub fn push_to_last_row(&mut self, val: TVal) {
+        self.try_push_to_last_row(val)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// See [`JaggedArray::try_push_to_last_row`].
+    #[inline]
+    pub fn try_push_to_last_row(&mut self, val: TVal) -> Result<(), JaggedArrayError> {
+        let last = match self.indices.last() {
+            Some(index) => Some(to_index(index.last().unwrap().as_() + 1)?),
+            None => None,
+        };
+        self.buffer.try_reserve(1)?;
+        self.buffer.push(val);
+        if let Some(last) = last {
+            *self.indices.last_mut().unwrap().last_mut().unwrap() = last;
+        }
+        Ok(())
+    }
+    #[inline]
+    pub fn pop_from_last_row(&mut self) -> Option<TVal> {
+        let Some(index) = self.indices.last_mut() else {
+            return self.buffer.pop();
+        };
+        let mut iter = index.iter_mut().rev();
+        let last = iter.next().unwrap();
+        if *last != TBuffer::TI::ZERO && iter.next().unwrap() < last {
+            *last -= TBuffer::TI::ONE;
+            self.buffer.pop()
+        } else {
+            None
+        }
+    }
+    /// # Panics
+    ///
+    /// Panics if the new offsets do not fit in the index type.
+    #[inline]
+    pub fn extend_last_row_from_slice(&mut self, values: &[TVal])
+    where
+        TVal: Clone,
+    {
+        self.try_extend_last_row_from_slice(values)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// See [`JaggedArray::try_extend_last_row_from_slice`].
+    #[inline]
+    pub fn try_extend_last_row_from_slice(
+        &mut self,
+        values: &[TVal],
+    ) -> Result<(), JaggedArrayError>
+    where
+        TVal: Clone,
+    {
+        let last = match self.indices.last() {
+            Some(index) => Some(to_index(index.last().unwrap().as_() + values.len())?),
+            None => None,
+        };
+        self.buffer.try_reserve(values.len())?;
+        self.buffer.extend_from_slice(values);
+        if let Some(last) = last {
+            *self.indices.last_mut().unwrap().last_mut().unwrap() = last;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, TVal, TNum> JaggedArrayDynView<'a, TVal, TNum>
+where
+    TNum: AsPrimitive<usize> + Num,
+{
+    /// Returns the number of dimensions.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.indices.len() + 1
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self.indices.first() {
+            Some(index) => index.len() - 1,
+            None => self.buffer.len(),
+        }
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+    #[inline]
+    pub fn buffer_len(&self) -> usize {
+        self.buffer.len()
+    }
+    /// Returns the values of the view in order.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [TVal] {
+        self.buffer
+    }
+    /// See [`JaggedArrayDyn::get`].
+    #[inline]
+    pub fn get(&self, index: &[usize]) -> Option<&'a TVal> {
+        let (&last, rows) = index.split_last()?;
+        if rows.len() != self.indices.len() {
+            return None;
+        }
+        let (start, end) = locate(self.indices.iter().copied(), self.len(), rows)?;
+        self.buffer[start..end].get(last)
+    }
+    /// See [`JaggedArrayDyn::view`].
+    #[inline]
+    pub fn view(&self, index: &[usize]) -> JaggedArrayDynView<'a, TVal, TNum> {
+        self.try_view(index)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// See [`JaggedArrayDyn::try_view`].
+    #[inline]
+    pub fn try_view(
+        &self,
+        index: &[usize],
+    ) -> Result<JaggedArrayDynView<'a, TVal, TNum>, JaggedArrayError> {
+        view_levels(self.indices.iter().copied(), self.buffer, index)
+    }
+    /// Copies the view into a new array whose offsets start from zero.
+    pub fn to_owned(&self) -> JaggedArrayDyn<TVal, Vec<TNum>>
+    where
+        TVal: Clone,
+    {
+        JaggedArrayDyn {
+            indices: self
+                .indices
+                .iter()
+                .map(|index| index.iter().map(|&x| x - index[0]).collect())
+                .collect(),
+            buffer: self.buffer.to_vec(),
+        }
+    }
+}
+
+impl<TVal, TBuffer: VecLike, const N: usize> From<JaggedArray<TVal, TBuffer, N>>
+    for JaggedArrayDyn<TVal, TBuffer>
+where
+    TBuffer::TI: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    #[inline]
+    fn from(value: JaggedArray<TVal, TBuffer, N>) -> Self {
+        Self {
+            indices: value.indices.into_iter().collect(),
+            buffer: value.buffer,
+        }
+    }
+}
+
+impl<TVal, TBuffer: VecLike, const N: usize> TryFrom<JaggedArrayDyn<TVal, TBuffer>>
+    for JaggedArray<TVal, TBuffer, N>
+where
+    TBuffer::TI: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Error = JaggedArrayError;
+    /// Fails with [`JaggedArrayError::DimensionMismatch`] if the array does not have `N` dimensions.
+    #[inline]
+    fn try_from(value: JaggedArrayDyn<TVal, TBuffer>) -> Result<Self, Self::Error> {
+        let actual = value.indices.len() + 1;
+        let indices = GenericArray::try_from_iter(value.indices).map_err(|_| {
+            JaggedArrayError::DimensionMismatch {
+                expected: N,
+                actual,
+            }
+        })?;
+        Ok(JaggedArray {
+            indices,
+            buffer: value.buffer,
+        })
+    }
+}
+
+impl<'a, TVal, TNum, const N: usize> From<JaggedArrayView<'a, TVal, TNum, N>>
+    for JaggedArrayDynView<'a, TVal, TNum>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    #[inline]
+    fn from(value: JaggedArrayView<'a, TVal, TNum, N>) -> Self {
+        Self {
+            indices: value.indices.into_iter().collect(),
+            buffer: value.buffer,
+        }
+    }
+}
+
+impl<'a, TVal, TNum, const N: usize> TryFrom<JaggedArrayDynView<'a, TVal, TNum>>
+    for JaggedArrayView<'a, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Error = JaggedArrayError;
+    /// Fails with [`JaggedArrayError::DimensionMismatch`] if the view does not have `N` dimensions.
+    #[inline]
+    fn try_from(value: JaggedArrayDynView<'a, TVal, TNum>) -> Result<Self, Self::Error> {
+        let actual = value.indices.len() + 1;
+        let indices = GenericArray::try_from_iter(value.indices).map_err(|_| {
+            JaggedArrayError::DimensionMismatch {
+                expected: N,
+                actual,
+            }
+        })?;
+        Ok(JaggedArrayView {
+            indices,
+            buffer: value.buffer,
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::TryReserveError;
+use std::fmt::{self, Display, Formatter};
+
+/// The error type for the fallible operations of jagged arrays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JaggedArrayError {
+    /// An offset does not fit in the index type of the array.
+    IndexOverflow,
+    /// An index or a range is out of the bounds of the array.
+    OutOfBounds,
+    /// The allocator failed to allocate the requested memory.
+    AllocationFailure(TryReserveError),
+    /// A fixed-capacity index buffer is full.
+    CapacityExhausted,
+    /// A level of offsets is empty instead of holding at least the leading zero.
+    MissingOffsets { level: usize },
+    /// A level of offsets starts at `offset` instead of zero.
+    NonZeroStart { level: usize, offset: usize },
+    /// The offset at `position` in a level is smaller than the one before it.
+    DecreasingOffsets { level: usize, position: usize },
+    /// The last offset of a level is `actual`, but the next level or the buffer holds `expected` entries.
+    LengthMismatch {
+        level: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// The input does not have the layout or the type the conversion expects.
+    InvalidFormat(&'static str),
+    /// The input contains null values, which jagged arrays cannot represent.
+    NullValues,
+    /// The input has `actual` dimensions, but the conversion expects `expected`.
+    DimensionMismatch { expected: usize, actual: usize },
+}
+
+impl Display for JaggedArrayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JaggedArrayError::IndexOverflow => {
+                write!(f, "offset does not fit in the index type")
+            }
+            JaggedArrayError::OutOfBounds => write!(f, "index out of bounds"),
+            JaggedArrayError::AllocationFailure(error) => error.fmt(f),
+            JaggedArrayError::CapacityExhausted => {
+                write!(f, "index buffer capacity exhausted")
+            }
+            JaggedArrayError::MissingOffsets { level } => {
+                write!(f, "offsets of level {level} are empty")
+            }
+            JaggedArrayError::NonZeroStart { level, offset } => {
+                write!(f, "offsets of level {level} start at {offset} instead of 0")
+            }
+            JaggedArrayError::DecreasingOffsets { level, position } => {
+                write!(
+                    f,
+                    "offsets of level {level} decrease at position {position}"
+                )
+            }
+            JaggedArrayError::LengthMismatch {
+                level,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "offsets of level {level} end at {actual}, but {expected} entries follow"
+            ),
+            JaggedArrayError::InvalidFormat(reason) => write!(f, "invalid format: {reason}"),
+            JaggedArrayError::NullValues => write!(f, "input contains null values"),
+            JaggedArrayError::DimensionMismatch { expected, actual } => {
+                write!(f, "expected {expected} dimensions, found {actual}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JaggedArrayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JaggedArrayError::AllocationFailure(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<TryReserveError> for JaggedArrayError {
+    #[inline]
+    fn from(error: TryReserveError) -> Self {
+        JaggedArrayError::AllocationFailure(error)
+    }
+}
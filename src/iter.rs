@@ -0,0 +1,570 @@
+use std::iter::{zip, Enumerate, FusedIterator};
+
+use generic_array::{ArrayLength, GenericArray};
+use num::traits::AsPrimitive;
+use num::Num;
+use typenum::{Const, Sub1, ToUInt, B1, U};
+
+use crate::jagged_array::{JaggedArrayMutView, JaggedArrayView};
+
+type Levels<'a, TNum, L> = GenericArray<&'a [TNum], L>;
+
+/// Splits every level of `indices` after the rows covering the first `mid` rows of their parent level.
+///
+/// Returns both halves of the levels and the position of the split in the buffer.
+#[inline]
+pub(crate) fn split_levels<'a, TNum, L>(
+    indices: &Levels<'a, TNum, L>,
+    mut mid: usize,
+) -> (Levels<'a, TNum, L>, Levels<'a, TNum, L>, usize)
+where
+    TNum: AsPrimitive<usize>,
+    L: ArrayLength,
+{
+    let mut left = indices.clone();
+    let mut right = indices.clone();
+    for (level, (left, right)) in indices.iter().zip(left.iter_mut().zip(right.iter_mut())) {
+        *left = &level[..mid + 1];
+        *right = &level[mid..];
+        mid = level[mid].as_() - level[0].as_();
+    }
+    (left, right, mid)
+}
+
+/// An iterator over the outermost rows of a jagged array or view.
+///
+/// Every row is yielded as a view with one dimension less(`R`) than the iterated array.
+/// This struct is created by [`rows`](crate::JaggedArrayViewTrait::rows).
+#[derive(Debug)]
+pub struct Rows<'a, TVal, TNum, const R: usize>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    offsets: &'a [TNum],
+    indices: GenericArray<&'a [TNum], Sub1<U<R>>>,
+    buffer: &'a [TVal],
+}
+
+impl<TVal, TNum, const R: usize> Clone for Rows<'_, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            offsets: self.offsets,
+            indices: self.indices.clone(),
+            buffer: self.buffer,
+        }
+    }
+}
+
+impl<'a, TVal, TNum, const R: usize> Rows<'a, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    /// `offsets` are the outermost offsets of the iterated rows,
+    /// `indices` and `buffer` are the remaining levels of the iterated array or view.
+    #[inline]
+    pub(crate) fn new(
+        offsets: &'a [TNum],
+        indices: GenericArray<&'a [TNum], Sub1<U<R>>>,
+        buffer: &'a [TVal],
+    ) -> Self {
+        Self {
+            offsets,
+            indices,
+            buffer,
+        }
+    }
+
+    /// Divides the remaining rows into two iterators at `mid`.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub(crate) fn split_at(self, mid: usize) -> (Self, Self) {
+        let (left, right, buffer_mid) =
+            split_levels(&self.indices, (self.offsets[mid] - self.offsets[0]).as_());
+        let (left_buffer, right_buffer) = self.buffer.split_at(buffer_mid);
+        (
+            Self::new(&self.offsets[..mid + 1], left, left_buffer),
+            Self::new(&self.offsets[mid..], right, right_buffer),
+        )
+    }
+}
+
+impl<'a, TVal, TNum, const R: usize> Iterator for Rows<'a, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    type Item = JaggedArrayView<'a, TVal, TNum, R>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match *self.offsets {
+            [start, end, ..] => {
+                self.offsets = &self.offsets[1..];
+                let (indices, remaining, mid) = split_levels(&self.indices, (end - start).as_());
+                let (buffer, remaining_buffer) = self.buffer.split_at(mid);
+                self.indices = remaining;
+                self.buffer = remaining_buffer;
+                Some(JaggedArrayView { indices, buffer })
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<TVal, TNum, const R: usize> DoubleEndedIterator for Rows<'_, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match *self.offsets {
+            [.., start, _] => {
+                let first = self.offsets[0];
+                self.offsets = &self.offsets[..self.offsets.len() - 1];
+                let (remaining, indices, mid) = split_levels(&self.indices, (start - first).as_());
+                let (remaining_buffer, buffer) = self.buffer.split_at(mid);
+                self.indices = remaining;
+                self.buffer = remaining_buffer;
+                Some(JaggedArrayView { indices, buffer })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<TVal, TNum, const R: usize> ExactSizeIterator for Rows<'_, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+}
+
+impl<TVal, TNum, const R: usize> FusedIterator for Rows<'_, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+}
+
+/// An iterator over the outermost rows of a jagged array or view that yields disjoint mutable views.
+///
+/// Every row is yielded as a mutable view with one dimension less(`R`) than the iterated array.
+/// This struct is created by [`rows_mut`](crate::JaggedArrayMutViewTrait::rows_mut).
+#[derive(Debug)]
+pub struct RowsMut<'a, TVal, TNum, const R: usize>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    offsets: &'a [TNum],
+    indices: GenericArray<&'a [TNum], Sub1<U<R>>>,
+    buffer: &'a mut [TVal],
+}
+
+impl<'a, TVal, TNum, const R: usize> RowsMut<'a, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    /// `offsets` are the outermost offsets of the iterated rows,
+    /// `indices` and `buffer` are the remaining levels of the iterated array or view.
+    #[inline]
+    pub(crate) fn new(
+        offsets: &'a [TNum],
+        indices: GenericArray<&'a [TNum], Sub1<U<R>>>,
+        buffer: &'a mut [TVal],
+    ) -> Self {
+        Self {
+            offsets,
+            indices,
+            buffer,
+        }
+    }
+
+    /// Divides the remaining rows into two iterators at `mid`.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub(crate) fn split_at(self, mid: usize) -> (Self, Self) {
+        let (left, right, buffer_mid) =
+            split_levels(&self.indices, (self.offsets[mid] - self.offsets[0]).as_());
+        let (left_buffer, right_buffer) = self.buffer.split_at_mut(buffer_mid);
+        (
+            Self::new(&self.offsets[..mid + 1], left, left_buffer),
+            Self::new(&self.offsets[mid..], right, right_buffer),
+        )
+    }
+}
+
+impl<'a, TVal, TNum, const R: usize> Iterator for RowsMut<'a, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    type Item = JaggedArrayMutView<'a, TVal, TNum, R>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match *self.offsets {
+            [start, end, ..] => {
+                self.offsets = &self.offsets[1..];
+                let (indices, remaining, mid) = split_levels(&self.indices, (end - start).as_());
+                let (buffer, remaining_buffer) = std::mem::take(&mut self.buffer).split_at_mut(mid);
+                self.indices = remaining;
+                self.buffer = remaining_buffer;
+                Some(JaggedArrayMutView { indices, buffer })
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<TVal, TNum, const R: usize> DoubleEndedIterator for RowsMut<'_, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match *self.offsets {
+            [.., start, _] => {
+                let first = self.offsets[0];
+                self.offsets = &self.offsets[..self.offsets.len() - 1];
+                let (remaining, indices, mid) = split_levels(&self.indices, (start - first).as_());
+                let (remaining_buffer, buffer) = std::mem::take(&mut self.buffer).split_at_mut(mid);
+                self.indices = remaining;
+                self.buffer = remaining_buffer;
+                Some(JaggedArrayMutView { indices, buffer })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<TVal, TNum, const R: usize> ExactSizeIterator for RowsMut<'_, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+}
+
+impl<TVal, TNum, const R: usize> FusedIterator for RowsMut<'_, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+}
+
+/// Computes the coordinates of the leaves of a jagged array visited in storage order.
+#[derive(Debug, Clone)]
+struct Coordinates<'a, TNum, const N: usize>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    indices: GenericArray<&'a [TNum], Sub1<U<N>>>,
+    // The row that contains the last visited position at every level
+    rows: GenericArray<usize, Sub1<U<N>>>,
+}
+
+impl<'a, TNum, const N: usize> Coordinates<'a, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    #[inline]
+    fn new(indices: GenericArray<&'a [TNum], Sub1<U<N>>>) -> Self {
+        Self {
+            indices,
+            rows: GenericArray::default(),
+        }
+    }
+
+    /// `leaf` must not be smaller than any leaf passed before.
+    #[inline]
+    fn coordinate(&mut self, leaf: usize) -> [usize; N] {
+        let mut index = [0; N];
+        let mut child = leaf;
+        for (level, (offsets, row)) in zip(self.indices.iter(), self.rows.iter_mut())
+            .enumerate()
+            .rev()
+        {
+            let base = offsets[0].as_();
+            while offsets[*row + 1].as_() - base <= child {
+                *row += 1;
+            }
+            index[level + 1] = child - (offsets[*row].as_() - base);
+            child = *row;
+        }
+        index[0] = child;
+        index
+    }
+}
+
+/// An iterator over the elements of a jagged array or view and their indices, in storage order.
+///
+/// This struct is created by [`iter_indexed`](crate::JaggedArrayViewTrait::iter_indexed).
+#[derive(Debug)]
+pub struct IndexedIter<'a, TVal, TNum, const N: usize>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    coordinates: Coordinates<'a, TNum, N>,
+    iter: Enumerate<std::slice::Iter<'a, TVal>>,
+}
+
+impl<TVal, TNum, const N: usize> Clone for IndexedIter<'_, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            coordinates: self.coordinates.clone(),
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, TVal, TNum, const N: usize> IndexedIter<'a, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    #[inline]
+    pub(crate) fn new(indices: GenericArray<&'a [TNum], Sub1<U<N>>>, buffer: &'a [TVal]) -> Self {
+        Self {
+            coordinates: Coordinates::new(indices),
+            iter: buffer.iter().enumerate(),
+        }
+    }
+}
+
+impl<'a, TVal, TNum, const N: usize> Iterator for IndexedIter<'a, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Item = ([usize; N], &'a TVal);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (leaf, value) = self.iter.next()?;
+        Some((self.coordinates.coordinate(leaf), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<TVal, TNum, const N: usize> ExactSizeIterator for IndexedIter<'_, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+}
+
+impl<TVal, TNum, const N: usize> FusedIterator for IndexedIter<'_, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+}
+
+/// A mutable iterator over the elements of a jagged array or view and their indices, in storage order.
+///
+/// This struct is created by [`iter_indexed_mut`](crate::JaggedArrayMutViewTrait::iter_indexed_mut).
+#[derive(Debug)]
+pub struct IndexedIterMut<'a, TVal, TNum, const N: usize>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    coordinates: Coordinates<'a, TNum, N>,
+    iter: Enumerate<std::slice::IterMut<'a, TVal>>,
+}
+
+impl<'a, TVal, TNum, const N: usize> IndexedIterMut<'a, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    #[inline]
+    pub(crate) fn new(
+        indices: GenericArray<&'a [TNum], Sub1<U<N>>>,
+        buffer: &'a mut [TVal],
+    ) -> Self {
+        Self {
+            coordinates: Coordinates::new(indices),
+            iter: buffer.iter_mut().enumerate(),
+        }
+    }
+}
+
+impl<'a, TVal, TNum, const N: usize> Iterator for IndexedIterMut<'a, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Item = ([usize; N], &'a mut TVal);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (leaf, value) = self.iter.next()?;
+        Some((self.coordinates.coordinate(leaf), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<TVal, TNum, const N: usize> ExactSizeIterator for IndexedIterMut<'_, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+}
+
+impl<TVal, TNum, const N: usize> FusedIterator for IndexedIterMut<'_, TVal, TNum, N>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+}
+
+/// An iterator over the number of children of every row at one depth of a jagged array or view.
+///
+/// This struct is created by [`row_lengths`](crate::JaggedArrayViewTrait::row_lengths).
+#[derive(Debug, Clone)]
+pub struct RowLengths<'a, TNum> {
+    windows: std::slice::Windows<'a, TNum>,
+}
+
+impl<'a, TNum> RowLengths<'a, TNum> {
+    #[inline]
+    pub(crate) fn new(offsets: &'a [TNum]) -> Self {
+        Self {
+            windows: offsets.windows(2),
+        }
+    }
+}
+
+impl<TNum> Iterator for RowLengths<'_, TNum>
+where
+    TNum: AsPrimitive<usize>,
+{
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.windows
+            .next()
+            .map(|pair| pair[1].as_() - pair[0].as_())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+}
+
+impl<TNum> DoubleEndedIterator for RowLengths<'_, TNum>
+where
+    TNum: AsPrimitive<usize>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.windows
+            .next_back()
+            .map(|pair| pair[1].as_() - pair[0].as_())
+    }
+}
+
+impl<TNum> ExactSizeIterator for RowLengths<'_, TNum> where TNum: AsPrimitive<usize> {}
+
+impl<TNum> FusedIterator for RowLengths<'_, TNum> where TNum: AsPrimitive<usize> {}
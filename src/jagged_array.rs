@@ -1,9 +1,11 @@
 use generic_array::{sequence::GenericSequence, ArrayLength, GenericArray};
 use num::traits::AsPrimitive;
+use num::traits::CheckedAdd;
 use num::traits::ConstOne;
 use num::traits::ConstZero;
 use num::traits::Num;
 use num::traits::NumAssignOps;
+use std::mem::MaybeUninit;
 use std::ops::Range;
 use std::{
     iter::zip,
@@ -11,6 +13,10 @@ use std::{
 };
 use typenum::{Const, IsEqual, NonZero, Sub1, ToUInt, Unsigned, B1, U, U2};
 
+use crate::error::JaggedArrayError;
+use crate::iter::{split_levels, IndexedIter, IndexedIterMut, RowLengths, Rows, RowsMut};
+#[cfg(feature = "rayon")]
+use crate::par_iter::{ParRows, ParRowsMut};
 use crate::vec_ext::Ext;
 use crate::vec_like::VecLike;
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -21,8 +27,8 @@ where
     <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
     Const<N>: ToUInt,
 {
-    indices: GenericArray<TBuffer, Sub1<U<N>>>,
-    buffer: Vec<TVal>,
+    pub(crate) indices: GenericArray<TBuffer, Sub1<U<N>>>,
+    pub(crate) buffer: Vec<TVal>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -33,8 +39,10 @@ where
     <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
     Const<N>: ToUInt,
 {
-    indices: GenericArray<&'a [TNum], Sub1<U<N>>>,
-    buffer: &'a [TVal],
+    // Every level is a window into the offsets of the viewed array,
+    // so the offsets of a level are relative to the first offset of that level.
+    pub(crate) indices: GenericArray<&'a [TNum], Sub1<U<N>>>,
+    pub(crate) buffer: &'a [TVal],
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -45,8 +53,8 @@ where
     <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
     Const<N>: ToUInt,
 {
-    indices: GenericArray<&'a mut [TNum], Sub1<U<N>>>,
-    buffer: &'a mut [TVal],
+    pub(crate) indices: GenericArray<&'a [TNum], Sub1<U<N>>>,
+    pub(crate) buffer: &'a mut [TVal],
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -57,8 +65,8 @@ where
     <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
     Const<N>: ToUInt,
 {
-    indices: GenericArray<Box<[TNum]>, Sub1<U<N>>>,
-    buffer: Box<[TVal]>,
+    pub(crate) indices: GenericArray<Box<[TNum]>, Sub1<U<N>>>,
+    pub(crate) buffer: Box<[TVal]>,
 }
 
 impl<TVal, TBuffer: VecLike, const N: usize> Default for JaggedArray<TVal, TBuffer, N>
@@ -83,8 +91,13 @@ where
 // Methods that are unique to JaggedArray
 impl<TVal, TBuffer: VecLike, const N: usize> JaggedArray<TVal, TBuffer, N>
 where
-    <TBuffer as VecLike>::TI:
-        AsPrimitive<usize> + Num + NumAssignOps + std::cmp::PartialOrd + ConstOne + ConstZero,
+    <TBuffer as VecLike>::TI: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + std::cmp::PartialOrd
+        + ConstOne
+        + ConstZero,
     U<N>: std::ops::Sub<B1>,
     <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
     Const<N>: ToUInt,
@@ -106,6 +119,73 @@ where
             buffer: Vec::with_capacity(*capacity.last().unwrap()),
         }
     }
+    /// Same as [`JaggedArray::with_capacity`], but returns an error instead of panicking or aborting
+    /// if memory cannot be allocated or a fixed-capacity index buffer is too small.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{JaggedArray, JaggedArrayError};
+    /// let data = JaggedArray::<usize, Vec<u16>, 2>::try_with_capacity([16, 256]).unwrap();
+    /// assert!(data.buffer_capacity() >= 256);
+    /// let data = JaggedArray::<usize, Vec<u16>, 2>::try_with_capacity([16, usize::MAX]);
+    /// assert!(matches!(data, Err(JaggedArrayError::AllocationFailure(_))));
+    /// ```
+    #[inline]
+    pub fn try_with_capacity(capacity: [usize; N]) -> Result<Self, JaggedArrayError> {
+        let mut result = Self::new();
+        result.try_reserve(capacity)?;
+        Ok(result)
+    }
+    /// Creates an array from its offsets and values, one level of offsets per dimension but the last.
+    ///
+    /// Every level must start at zero, never decrease and end at the number of rows in the next level,
+    /// or at `buffer.len()` for the last level. Returns an error describing the first violation otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, GenericArray, JaggedArray, JaggedArrayError};
+    /// let data = JaggedArray::from_raw_parts(GenericArray::from([vec![0u16, 2, 3]]), vec![1, 2, 3]);
+    /// assert!(data.unwrap() == jagged![u16; [1, 2], [3]]);
+    /// let data = JaggedArray::<_, Vec<u16>, 2>::from_raw_parts(GenericArray::from([vec![0, 2, 4]]), vec![1, 2, 3]);
+    /// assert!(data == Err(JaggedArrayError::LengthMismatch { level: 0, expected: 3, actual: 4 }));
+    /// ```
+    pub fn from_raw_parts(
+        indices: GenericArray<TBuffer, Sub1<U<N>>>,
+        buffer: Vec<TVal>,
+    ) -> Result<Self, JaggedArrayError> {
+        let result = Self { indices, buffer };
+        result.validate()?;
+        Ok(result)
+    }
+    /// Creates an array from its offsets and values without checking them.
+    ///
+    /// # Safety
+    ///
+    /// The offsets must pass [`JaggedArray::validate`].
+    #[inline]
+    pub unsafe fn from_raw_parts_unchecked(
+        indices: GenericArray<TBuffer, Sub1<U<N>>>,
+        buffer: Vec<TVal>,
+    ) -> Self {
+        Self { indices, buffer }
+    }
+    /// Decomposes the array into its offsets and values.
+    /// See [`JaggedArray::from_raw_parts`] for the layout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// let (indices, buffer) = jagged![u16; [1, 2], [3]].into_raw_parts();
+    /// assert!(indices[0] == [0, 2, 3]);
+    /// assert!(buffer == [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn into_raw_parts(self) -> (GenericArray<TBuffer, Sub1<U<N>>>, Vec<TVal>) {
+        (self.indices, self.buffer)
+    }
     #[inline]
     pub fn reserve(&mut self, additional: [usize; N]) {
         for (index, additional) in zip(self.indices.iter_mut(), additional.iter()) {
@@ -113,6 +193,26 @@ where
         }
         self.buffer.reserve(additional[N - 1]);
     }
+    /// Same as [`JaggedArray::reserve`], but returns an error instead of panicking or aborting
+    /// if memory cannot be allocated or a fixed-capacity index buffer is too small.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{JaggedArray, JaggedArrayError};
+    /// use tinyvec::ArrayVec;
+    /// let mut data = JaggedArray::<usize, ArrayVec<[u16; 8]>, 2>::new();
+    /// assert!(data.try_reserve([7, 100]).is_ok());
+    /// assert!(data.try_reserve([8, 100]) == Err(JaggedArrayError::CapacityExhausted));
+    /// ```
+    #[inline]
+    pub fn try_reserve(&mut self, additional: [usize; N]) -> Result<(), JaggedArrayError> {
+        for (index, additional) in zip(self.indices.iter_mut(), additional.iter()) {
+            index.try_reserve(*additional)?;
+        }
+        self.buffer.try_reserve(additional[N - 1])?;
+        Ok(())
+    }
     #[inline]
     pub fn buffer_reserve(&mut self, additional: usize) {
         self.buffer.reserve(additional);
@@ -144,8 +244,39 @@ where
     /// data.push_to_last_row(1);
     /// assert!(data[[1,0]] == 1);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new offsets do not fit in the index type.
     #[inline]
     pub fn new_row<const DIM: usize>(&mut self)
+    where
+        U<N>: std::ops::Sub<U<DIM>>,
+        Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output>: Unsigned + NonZero,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: std::ops::Sub<typenum::B1>,
+        U<DIM>: ArrayLength,
+        Const<N>: ToUInt,
+        Const<DIM>: ToUInt,
+    {
+        self.try_new_row::<DIM>()
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Same as [`JaggedArray::new_row`], but returns an error instead of panicking
+    /// if the new offsets do not fit in the index type or the index buffer cannot grow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{JaggedArray, JaggedArrayError};
+    /// let mut data = JaggedArray::<usize, Vec<u8>, 3>::new();
+    /// data.new_row::<0>();
+    /// for _ in 0..255 {
+    ///     data.try_new_row::<1>().unwrap();
+    /// }
+    /// assert!(data.try_new_row::<1>() == Err(JaggedArrayError::IndexOverflow));
+    /// ```
+    #[inline]
+    pub fn try_new_row<const DIM: usize>(&mut self) -> Result<(), JaggedArrayError>
     where
         U<N>: std::ops::Sub<U<DIM>>,
         Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output>: Unsigned + NonZero,
@@ -155,24 +286,65 @@ where
         Const<DIM>: ToUInt,
     {
         let m = DIM;
+        let parent = match m.checked_sub(1) {
+            Some(level) => Some(to_index(self.indices[level].last().unwrap().as_() + 1)?),
+            None => None,
+        };
         let buffer = &mut self.indices[m];
         let new_val = *buffer.last().unwrap();
-        buffer.push(new_val);
-        if m > 0 {
-            *self.indices[m - 1].last_mut().unwrap() += TBuffer::TI::ONE;
+        buffer.try_push(new_val)?;
+        if let Some(parent) = parent {
+            *self.indices[m - 1].last_mut().unwrap() = parent;
         }
+        Ok(())
     }
+    /// # Panics
+    ///
+    /// Panics if the new offsets do not fit in the index type.
     #[inline]
     pub fn push_to_last_row(&mut self, val: TVal) {
+        self.try_push_to_last_row(val)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Same as [`JaggedArray::push_to_last_row`], but returns an error instead of panicking
+    /// if the new offsets do not fit in the index type or memory cannot be allocated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{JaggedArray, JaggedArrayError};
+    /// let mut data = JaggedArray::<usize, Vec<u8>, 2>::new();
+    /// data.new_row::<0>();
+    /// for i in 0..255 {
+    ///     data.try_push_to_last_row(i).unwrap();
+    /// }
+    /// assert!(data.try_push_to_last_row(255) == Err(JaggedArrayError::IndexOverflow));
+    /// assert!(data.buffer_len() == 255);
+    /// ```
+    #[inline]
+    pub fn try_push_to_last_row(&mut self, val: TVal) -> Result<(), JaggedArrayError> {
+        let last = match self.indices.last() {
+            Some(index) => Some(
+                index
+                    .last()
+                    .unwrap()
+                    .checked_add(&TBuffer::TI::ONE)
+                    .ok_or(JaggedArrayError::IndexOverflow)?,
+            ),
+            None => None,
+        };
+        self.buffer.try_reserve(1)?;
         self.buffer.push(val);
-        if let Some(value) = self.indices.last_mut() {
-            *value.last_mut().unwrap() += TBuffer::TI::ONE;
+        if let Some(last) = last {
+            *self.indices.last_mut().unwrap().last_mut().unwrap() = last;
         }
+        Ok(())
     }
     #[inline]
     /// # Safety
     ///
     /// The caller must ensure that `self.buffer_len()` < `self.buffer_capacity()`
+    /// and that the new offsets fit in the index type
     pub unsafe fn push_to_last_row_unchecked(&mut self, val: TVal) {
         unsafe { self.buffer.unchecked_push(val) };
         if let Some(value) = self.indices.last_mut() {
@@ -211,12 +383,48 @@ where
     /// assert!(data[[0,1]] == 2);
     /// assert!(data[[0,2]] == 3);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new offsets do not fit in the index type.
     #[inline]
     pub fn extend_last_row(&mut self, values: impl Iterator<Item = TVal>) {
+        self.try_extend_last_row(values)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Same as [`JaggedArray::extend_last_row`], but returns an error instead of panicking
+    /// if the new offsets do not fit in the index type or memory cannot be allocated.
+    /// The array is left unchanged on error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{JaggedArray, JaggedArrayError};
+    /// let mut data = JaggedArray::<usize, Vec<u8>, 2>::new();
+    /// data.new_row::<0>();
+    /// assert!(data.try_extend_last_row(0..256) == Err(JaggedArrayError::IndexOverflow));
+    /// assert!(data.buffer_len() == 0);
+    /// assert!(data.try_extend_last_row(0..255).is_ok());
+    /// ```
+    #[inline]
+    pub fn try_extend_last_row(
+        &mut self,
+        values: impl Iterator<Item = TVal>,
+    ) -> Result<(), JaggedArrayError> {
         let initial = self.buffer.len();
+        self.buffer.try_reserve(values.size_hint().0)?;
         self.buffer.extend(values);
-        *self.indices.last_mut().unwrap().last_mut().unwrap() +=
-            (self.buffer.len() - initial).as_();
+        if let Some(index) = self.indices.last_mut() {
+            let last = index.last_mut().unwrap();
+            match to_index(last.as_() + self.buffer.len() - initial) {
+                Ok(value) => *last = value,
+                Err(error) => {
+                    self.buffer.truncate(initial);
+                    return Err(error);
+                }
+            }
+        }
+        Ok(())
     }
     /// # Example
     ///
@@ -229,15 +437,48 @@ where
     /// assert!(data[[0,1]] == 2);
     /// assert!(data[[0,2]] == 3);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new offsets do not fit in the index type.
     #[inline]
     pub fn extend_last_row_from_slice(&mut self, values: &[TVal])
     where
         TVal: Clone,
     {
-        let initial = self.buffer.len();
+        self.try_extend_last_row_from_slice(values)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Same as [`JaggedArray::extend_last_row_from_slice`], but returns an error instead of panicking
+    /// if the new offsets do not fit in the index type or memory cannot be allocated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{JaggedArray, JaggedArrayError};
+    /// let mut data = JaggedArray::<usize, Vec<u8>, 2>::new();
+    /// data.new_row::<0>();
+    /// assert!(data.try_extend_last_row_from_slice(&[0; 256]) == Err(JaggedArrayError::IndexOverflow));
+    /// assert!(data.try_extend_last_row_from_slice(&[0; 255]).is_ok());
+    /// ```
+    #[inline]
+    pub fn try_extend_last_row_from_slice(
+        &mut self,
+        values: &[TVal],
+    ) -> Result<(), JaggedArrayError>
+    where
+        TVal: Clone,
+    {
+        let last = match self.indices.last() {
+            Some(index) => Some(to_index(index.last().unwrap().as_() + values.len())?),
+            None => None,
+        };
+        self.buffer.try_reserve(values.len())?;
         self.buffer.extend_from_slice(values);
-        *self.indices.last_mut().unwrap().last_mut().unwrap() +=
-            (self.buffer.len() - initial).as_();
+        if let Some(last) = last {
+            *self.indices.last_mut().unwrap().last_mut().unwrap() = last;
+        }
+        Ok(())
     }
     /// # Example
     ///
@@ -274,17 +515,31 @@ where
         Const<M>: ToUInt,
         TVal: Clone,
     {
-        let skipped = N - M;
-        if skipped == N - 1 {
-            *self.indices[skipped - 1].last_mut().unwrap() += other.len().as_();
-        } else if skipped != 0 {
-            *self.indices[skipped - 1].last_mut().unwrap() += TBuffer::TI::ONE;
-        }
-        for (dst, src) in zip(self.indices.iter_mut().skip(skipped), other.indices.iter()) {
-            let last = *dst.last().unwrap();
-            dst.extend(src.iter().skip(1).map(|&x| x + last));
-        }
+        self.try_append_from_view(other)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Same as [`JaggedArray::append_from_view`], but returns an error instead of panicking
+    /// if the new offsets do not fit in the index type or memory cannot be allocated.
+    /// `self` is left unchanged on error.
+    pub fn try_append_from_view<const M: usize>(
+        &mut self,
+        other: JaggedArrayView<TVal, TBuffer::TI, M>,
+    ) -> Result<(), JaggedArrayError>
+    where
+        U<N>: std::ops::Sub<U<M>>,
+        <U<N> as std::ops::Sub<U<M>>>::Output: Unsigned,
+        U<M>: std::ops::Sub<B1>,
+        <U<M> as std::ops::Sub<B1>>::Output: ArrayLength,
+        U<M>: ArrayLength,
+        Const<N>: ToUInt,
+        Const<M>: ToUInt,
+        TVal: Clone,
+    {
+        self.buffer.try_reserve(other.buffer.len())?;
+        self.append_levels(N - M, other.len(), other.indices.iter().copied())?;
         self.buffer.extend_from_slice(other.buffer);
+        self.debug_check_ends();
+        Ok(())
     }
     /// # Example
     ///
@@ -304,6 +559,10 @@ where
     /// assert!(data[[1,1]] == 5);
     /// assert!(data[[1,2]] == 6);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new offsets do not fit in the index type.
     pub fn append<const M: usize>(&mut self, other: JaggedArray<TVal, TBuffer, M>)
     where
         U<N>: std::ops::Sub<U<M>>,
@@ -314,18 +573,217 @@ where
         Const<N>: ToUInt,
         Const<M>: ToUInt,
     {
-        let skipped = N - M;
-        if skipped == N - 1 {
-            *self.indices[skipped - 1].last_mut().unwrap() += other.len().as_();
-        } else if skipped != 0 {
-            *self.indices[skipped - 1].last_mut().unwrap() += TBuffer::TI::ONE;
+        self.try_append(other)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Same as [`JaggedArray::append`], but returns an error instead of panicking
+    /// if the new offsets do not fit in the index type or memory cannot be allocated.
+    /// `self` is left unchanged on error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{JaggedArray, JaggedArrayError, JaggedArrayViewTrait};
+    /// let mut data = JaggedArray::<usize, Vec<u8>, 2>::from(vec![vec![0; 200]]);
+    /// let other = JaggedArray::from(vec![vec![0; 100]]);
+    /// assert!(data.try_append(other) == Err(JaggedArrayError::IndexOverflow));
+    /// assert!(data.try_append(JaggedArray::from(vec![vec![0; 50]])).is_ok());
+    /// assert!(data.len() == 2);
+    /// ```
+    pub fn try_append<const M: usize>(
+        &mut self,
+        other: JaggedArray<TVal, TBuffer, M>,
+    ) -> Result<(), JaggedArrayError>
+    where
+        U<N>: std::ops::Sub<U<M>>,
+        <U<N> as std::ops::Sub<U<M>>>::Output: Unsigned,
+        U<M>: std::ops::Sub<B1>,
+        <U<M> as std::ops::Sub<B1>>::Output: ArrayLength,
+        U<M>: ArrayLength,
+        Const<N>: ToUInt,
+        Const<M>: ToUInt,
+    {
+        let rows = other
+            .indices
+            .first()
+            .map_or(other.buffer.len(), |index| index.len() - 1);
+        self.buffer.try_reserve(other.buffer.len())?;
+        self.append_levels(N - M, rows, other.indices.iter().map(|index| &index[..]))?;
+        self.buffer.extend(other.buffer);
+        self.debug_check_ends();
+        Ok(())
+    }
+    /// Checks that every level starts at zero and ends where the next level or the buffer does.
+    /// Unlike [`JaggedArray::validate`] this only looks at the ends of each level,
+    /// so it stays cheap enough to run after every append or removal in debug builds.
+    fn debug_check_ends(&self) {
+        if cfg!(debug_assertions) {
+            let lengths = self
+                .indices
+                .iter()
+                .skip(1)
+                .map(|index| index.len() - 1)
+                .chain(std::iter::once(self.buffer.len()));
+            for (index, expected) in zip(self.indices.iter(), lengths) {
+                assert_eq!(index.first().map(|x| x.as_()), Some(0));
+                assert_eq!(index.last().map(|x| x.as_()), Some(expected));
+            }
+        }
+    }
+    /// Appends `levels`, the offsets of an array with `rows` outermost rows,
+    /// to the last row of dimension `skipped - 1`.
+    ///
+    /// All new offsets are checked before `self` is modified.
+    fn append_levels<'b>(
+        &mut self,
+        skipped: usize,
+        rows: usize,
+        levels: impl Iterator<Item = &'b [TBuffer::TI]> + Clone,
+    ) -> Result<(), JaggedArrayError>
+    where
+        TBuffer::TI: 'b,
+    {
+        let parent = match skipped.checked_sub(1) {
+            Some(level) => Some(to_index(self.indices[level].last().unwrap().as_() + rows)?),
+            None => None,
+        };
+        for (dst, src) in zip(self.indices.iter().skip(skipped), levels.clone()) {
+            to_index::<TBuffer::TI>(
+                dst.last().unwrap().as_() + src[src.len() - 1].as_() - src[0].as_(),
+            )?;
+        }
+        for (dst, src) in zip(self.indices.iter_mut().skip(skipped), levels.clone()) {
+            dst.try_reserve(src.len() - 1)?;
         }
-        for (dst, src) in zip(self.indices.iter_mut().skip(skipped), other.indices.iter()) {
+        if let Some(parent) = parent {
+            *self.indices[skipped - 1].last_mut().unwrap() = parent;
+        }
+        for (dst, src) in zip(self.indices.iter_mut().skip(skipped), levels) {
             let last = *dst.last().unwrap();
-            dst.extend(src.iter().skip(1).map(|&x| x + last));
+            let base = src[0];
+            dst.extend(src.iter().skip(1).map(|&x| x - base + last));
         }
-        self.buffer.extend(other.buffer);
+        Ok(())
+    }
+    /// Inserts `row` at `index`, shifting all following rows of the same parent.
+    ///
+    /// Rust const generics does not support arithmetic, so we have to specify the row's dimension(R) as well
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parent row of `index` does not exist or the last value of `index` is greater than its length,
+    /// or if the new offsets do not fit in the index type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// let mut data = jagged![[[1, 2], [3]], [[4]]];
+    /// data.insert_row::<2, 1>([0, 1], jagged![5, 6]);
+    /// data.insert_row::<1, 2>([1], jagged![[7], [8, 9]]);
+    /// assert!(
+    ///     data.to_nested_vec()
+    ///         == vec![
+    ///             vec![vec![1, 2], vec![5, 6], vec![3]],
+    ///             vec![vec![7], vec![8, 9]],
+    ///             vec![vec![4]]
+    ///         ]
+    /// );
+    /// ```
+    pub fn insert_row<const M: usize, const R: usize>(
+        &mut self,
+        index: [usize; M],
+        row: JaggedArray<TVal, TBuffer, R>,
+    ) where
+        U<M>: NonZero,
+        U<N>: std::ops::Sub<U<M>>,
+        <U<N> as std::ops::Sub<U<M>>>::Output: IsEqual<U<R>>,
+        <<U<N> as std::ops::Sub<U<M>>>::Output as IsEqual<U<R>>>::Output: NonZero,
+        U<R>: std::ops::Sub<B1>,
+        <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+        Const<M>: ToUInt,
+        Const<R>: ToUInt,
+    {
+        self.try_insert_row(index, row)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Same as [`JaggedArray::insert_row`], but returns an error instead of panicking.
+    /// `self` is left unchanged on error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, JaggedArrayError};
+    /// let mut data = jagged![[1, 2], [3]];
+    /// assert!(data.try_insert_row::<1, 1>([3], jagged![4]) == Err(JaggedArrayError::OutOfBounds));
+    /// assert!(data.try_insert_row::<1, 1>([2], jagged![4]).is_ok());
+    /// assert!(data.to_nested_vec() == vec![vec![1, 2], vec![3], vec![4]]);
+    /// ```
+    pub fn try_insert_row<const M: usize, const R: usize>(
+        &mut self,
+        index: [usize; M],
+        row: JaggedArray<TVal, TBuffer, R>,
+    ) -> Result<(), JaggedArrayError>
+    where
+        U<M>: NonZero,
+        U<N>: std::ops::Sub<U<M>>,
+        <U<N> as std::ops::Sub<U<M>>>::Output: IsEqual<U<R>>,
+        <<U<N> as std::ops::Sub<U<M>>>::Output as IsEqual<U<R>>>::Output: NonZero,
+        U<R>: std::ops::Sub<B1>,
+        <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+        Const<M>: ToUInt,
+        Const<R>: ToUInt,
+    {
+        let depth = M - 1;
+        let (parent, children) = self.children_of(&index[..depth])?;
+        if index[depth] > children.len() {
+            return Err(JaggedArrayError::OutOfBounds);
+        }
+        let id = children.start + index[depth];
+        let count = row
+            .indices
+            .first()
+            .map_or(row.buffer.len(), |index| index.len() - 1);
+        // All new offsets are checked before anything is modified
+        if depth > 0 {
+            to_index::<TBuffer::TI>(self.indices[depth - 1].last().unwrap().as_() + 1)?;
+        }
+        to_index::<TBuffer::TI>(self.indices[depth].last().unwrap().as_() + count)?;
+        for (dst, src) in zip(self.indices.iter().skip(M), row.indices.iter()) {
+            to_index::<TBuffer::TI>(dst.last().unwrap().as_() + src[src.len() - 1].as_())?;
+        }
+        self.indices[depth].try_reserve(1)?;
+        for (dst, src) in zip(self.indices.iter_mut().skip(M), row.indices.iter()) {
+            dst.try_reserve(src.len() - 1)?;
+        }
+        self.buffer.try_reserve(row.buffer.len())?;
+        if let Some(parent) = parent {
+            for i in self.indices[depth - 1][parent + 1..].iter_mut() {
+                *i += TBuffer::TI::ONE;
+            }
+        }
+        let level = &mut self.indices[depth];
+        let mut position = level[id];
+        level.insert(id, position);
+        for i in level[id + 1..].iter_mut() {
+            *i += count.as_();
+        }
+        for (dst, src) in zip(self.indices.iter_mut().skip(M), row.indices.iter()) {
+            let offset = dst[position.as_()];
+            let count = src.len() - 1;
+            let total = src[count];
+            dst.insert_iter(position.as_(), src.iter().take(count).map(|&x| x + offset));
+            for i in dst[position.as_() + count..].iter_mut() {
+                *i += total;
+            }
+            position = offset;
+        }
+        let position = position.as_();
+        self.buffer.splice(position..position, row.buffer);
+        Ok(())
     }
+    /// See [`JaggedArray::pop_row`] for a variant that returns the removed row.
+    ///
     /// # Example
     ///
     /// ```
@@ -336,8 +794,8 @@ where
     /// data.extend_last_row_from_slice(&[1,2,3]);
     /// data.remove_last_row::<0>();
     /// assert!(data.is_empty());
+    /// assert!(!data.remove_last_row::<0>());
     /// ```
-    // It may be possible to implement a drain-like variant of this method
     pub fn remove_last_row<const DIM: usize>(&mut self) -> bool
     where
         U<N>: std::ops::Sub<U<DIM>>,
@@ -347,7 +805,34 @@ where
         Const<N>: ToUInt,
         Const<DIM>: ToUInt,
     {
-        self.truncate::<DIM>(self.indices[DIM].len() - 2)
+        self.try_remove_last_row::<DIM>().is_ok()
+    }
+    /// Same as [`JaggedArray::remove_last_row`], but returns [`JaggedArrayError::OutOfBounds`]
+    /// instead of `false` if there is no row to remove.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, JaggedArray, JaggedArrayError};
+    /// let mut data = jagged![u16; [1, 2], [3]];
+    /// assert!(data.try_remove_last_row::<0>().is_ok());
+    /// assert!(data.try_remove_last_row::<0>().is_ok());
+    /// assert!(data.try_remove_last_row::<0>() == Err(JaggedArrayError::OutOfBounds));
+    /// ```
+    pub fn try_remove_last_row<const DIM: usize>(&mut self) -> Result<(), JaggedArrayError>
+    where
+        U<N>: std::ops::Sub<U<DIM>>,
+        Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output>: Unsigned + NonZero,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: std::ops::Sub<typenum::B1>,
+        U<DIM>: ArrayLength,
+        Const<N>: ToUInt,
+        Const<DIM>: ToUInt,
+    {
+        let rows = self.indices[DIM]
+            .len()
+            .checked_sub(2)
+            .ok_or(JaggedArrayError::OutOfBounds)?;
+        self.try_truncate::<DIM>(rows)
     }
     /// # Example
     ///
@@ -368,71 +853,911 @@ where
     /// assert!(data[[1, 1]] == 8);
     /// assert!(data[[1, 2]] == 9);
     /// ```
-    pub fn remove_rows(&mut self, mut range: Range<usize>)
+    pub fn remove_rows(&mut self, range: Range<usize>)
     where
         U<N>: NonZero,
     {
-        let (first, second) = self.indices.split_at_mut(1);
-        let first = &mut first[0];
-        let start = first[range.start].as_();
-        let end = first[range.end].as_();
-        first.remove_range(range.clone());
-        for i in first[range.start..].iter_mut() {
-            *i -= (end - start).as_();
-        }
-        range.start = start;
-        range.end = end;
-        for index in second {
-            let end = index[start].as_();
-            let start = index[start - 1].as_();
-            index.remove_range(range.clone());
-            for i in index[start..].iter_mut() {
-                *i -= (end - start).as_();
-            }
-            range.start = start;
-            range.end = end;
-        }
-        self.buffer.drain(range).for_each(drop);
+        self.try_remove_rows(range)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Same as [`JaggedArray::remove_rows`], but returns an error instead of panicking
+    /// if `range` is out of bounds.
+    pub fn try_remove_rows(&mut self, range: Range<usize>) -> Result<(), JaggedArrayError>
+    where
+        U<N>: NonZero,
+    {
+        self.drain_rows_in(&[], range, |_| {})?.for_each(drop);
+        self.debug_check_ends();
+        Ok(())
     }
+    /// Removes the rows in `range` from the row at `parent_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row at `parent_index` does not exist or `range` is out of its bounds.
+    ///
     /// # Example
     ///
     /// ```
-    /// use jaggedarray::jagged_array::JaggedArray;
-    /// use crate::jaggedarray::jagged_array::JaggedArrayViewTrait;
-    /// let mut data = JaggedArray::<usize, Vec<u16>, 2>::new();
-    /// data.new_row::<0>();
-    /// data.extend_last_row_from_slice(&[1,2,3]);
-    /// data.truncate::<0>(1);
-    /// assert!(data[[0,0]] == 1);
+    /// use jaggedarray::jagged;
+    /// let mut data = jagged![[[1, 2], [3], [4]], [[5, 6], [7]]];
+    /// data.remove_rows_at::<1>([0], 0..2);
+    /// data.remove_rows_at::<2>([1, 0], 1..2);
+    /// assert!(data.to_nested_vec() == vec![vec![vec![4]], vec![vec![5], vec![7]]]);
     /// ```
-    pub fn truncate<const DIM: usize>(&mut self, row_length: usize) -> bool {
-        if row_length < self.indices[DIM].len() {
-            self.indices[DIM].truncate(row_length + 1);
-            let mut end = self.indices[DIM][row_length];
-            for index in self.indices.iter_mut().skip(DIM + 1) {
-                index.truncate(end.as_() + 1);
-                end = *index.last().unwrap();
-            }
-            self.buffer.truncate(end.as_());
-            true
-        } else {
-            false
+    pub fn remove_rows_at<const DIM: usize>(
+        &mut self,
+        parent_index: [usize; DIM],
+        range: Range<usize>,
+    ) where
+        U<N>: std::ops::Sub<U<DIM>>,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: NonZero,
+        Const<DIM>: ToUInt,
+    {
+        self.try_remove_rows_at(parent_index, range)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+    /// Same as [`JaggedArray::remove_rows_at`], but returns an error instead of panicking
+    /// if the row at `parent_index` does not exist or `range` is out of its bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, JaggedArrayError};
+    /// let mut data = jagged![[[1, 2], [3]], [[4]]];
+    /// assert!(data.try_remove_rows_at::<1>([2], 0..1) == Err(JaggedArrayError::OutOfBounds));
+    /// assert!(data.try_remove_rows_at::<2>([1, 0], 0..2) == Err(JaggedArrayError::OutOfBounds));
+    /// assert!(data.try_remove_rows_at::<2>([0, 0], 1..2).is_ok());
+    /// assert!(data.to_nested_vec() == vec![vec![vec![1], vec![3]], vec![vec![4]]]);
+    /// ```
+    pub fn try_remove_rows_at<const DIM: usize>(
+        &mut self,
+        parent_index: [usize; DIM],
+        range: Range<usize>,
+    ) -> Result<(), JaggedArrayError>
+    where
+        U<N>: std::ops::Sub<U<DIM>>,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: NonZero,
+        Const<DIM>: ToUInt,
+    {
+        self.drain_rows_in(&parent_index, range, |_| {})?
+            .for_each(drop);
+        self.debug_check_ends();
+        Ok(())
+    }
+    /// Removes the outermost rows in `range` and returns them as a new array, moving the values out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// let mut data = jagged![[1, 2], [3], [4, 5, 6]];
+    /// let drained = data.drain_rows(1..3);
+    /// assert!(drained.to_nested_vec() == vec![vec![3], vec![4, 5, 6]]);
+    /// assert!(data.to_nested_vec() == vec![vec![1, 2]]);
+    /// ```
+    pub fn drain_rows(&mut self, range: Range<usize>) -> JaggedArray<TVal, TBuffer, N>
+    where
+        U<N>: NonZero,
+    {
+        self.try_drain_rows(range)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Same as [`JaggedArray::drain_rows`], but returns an error instead of panicking
+    /// if `range` is out of bounds.
+    pub fn try_drain_rows(
+        &mut self,
+        range: Range<usize>,
+    ) -> Result<JaggedArray<TVal, TBuffer, N>, JaggedArrayError>
+    where
+        U<N>: NonZero,
+    {
+        let mut indices = Vec::with_capacity(N - 1);
+        let buffer = self
+            .drain_rows_in(&[], range, |offsets| indices.push(rebase(offsets)))?
+            .collect();
+        Ok(JaggedArray {
+            indices: indices.into_iter().collect(),
+            buffer,
+        })
+    }
+    /// Splits the array into two at the outermost row `at`.
+    ///
+    /// Returns a newly allocated array containing the rows `[at, len)`, moving the values out of `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// let mut data = jagged![[[1], [2, 3]], [[4]], [[5, 6]]];
+    /// let other = data.split_off(1);
+    /// assert!(data.to_nested_vec() == vec![vec![vec![1], vec![2, 3]]]);
+    /// assert!(other.to_nested_vec() == vec![vec![vec![4]], vec![vec![5, 6]]]);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Self {
+        self.try_split_off(at)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Same as [`JaggedArray::split_off`], but returns an error instead of panicking if `at > len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, JaggedArrayError, JaggedArrayViewTrait};
+    /// let mut data = jagged![[1], [2, 3]];
+    /// assert!(data.try_split_off(3) == Err(JaggedArrayError::OutOfBounds));
+    /// assert!(data.try_split_off(2).unwrap().is_empty());
+    /// ```
+    pub fn try_split_off(&mut self, at: usize) -> Result<Self, JaggedArrayError> {
+        let len = self
+            .indices
+            .first()
+            .map_or(self.buffer.len(), |index| index.len() - 1);
+        if at > len {
+            return Err(JaggedArrayError::OutOfBounds);
+        }
+        let mut mid = at;
+        let indices = self
+            .indices
+            .iter_mut()
+            .map(|index| {
+                let tail = rebase(&index[mid..]);
+                let next = index[mid].as_();
+                index.truncate(mid + 1);
+                mid = next;
+                tail
+            })
+            .collect();
+        Ok(Self {
+            indices,
+            buffer: self.buffer.split_off(mid),
+        })
+    }
+    /// Removes the last row of dimension `DIM` from the last row of dimension `DIM - 1` and returns it.
+    /// Returns `None` if there is no such row.
+    ///
+    /// Rust const generics does not support arithmetic, so we have to specify the row's dimension(R) as well
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, JaggedArray1DViewTrait};
+    /// let mut data = jagged![[[1, 2], [3]], [[4], [5, 6]]];
+    /// assert!(data.pop_row::<1, 1>().unwrap().as_slice() == [5, 6]);
+    /// assert!(data.pop_row::<0, 2>().unwrap().to_nested_vec() == vec![vec![4]]);
+    /// assert!(data.to_nested_vec() == vec![vec![vec![1, 2], vec![3]]]);
+    /// ```
+    pub fn pop_row<const DIM: usize, const R: usize>(
+        &mut self,
+    ) -> Option<JaggedArray<TVal, TBuffer, R>>
+    where
+        U<N>: std::ops::Sub<U<DIM>>,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: std::ops::Sub<B1>,
+        Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output>: IsEqual<U<R>>,
+        <Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output> as IsEqual<U<R>>>::Output: NonZero,
+        U<R>: std::ops::Sub<B1>,
+        <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+        Const<DIM>: ToUInt,
+        Const<R>: ToUInt,
+    {
+        let (parent, children) = match DIM.checked_sub(1) {
+            Some(level) => {
+                let index = &self.indices[level];
+                let parent = index.len().checked_sub(2)?;
+                (Some(parent), index[parent].as_()..index[parent + 1].as_())
+            }
+            None => (None, 0..self.indices[0].len() - 1),
+        };
+        if children.is_empty() {
+            return None;
+        }
+        let mut indices = Vec::with_capacity(R);
+        let buffer = self
+            .drain_range(DIM, parent, children.end - 1..children.end, |offsets| {
+                indices.push(rebase(offsets))
+            })
+            .collect();
+        // The first level only holds the offsets of the popped row itself
+        Some(JaggedArray {
+            indices: indices.into_iter().skip(1).collect(),
+            buffer,
+        })
+    }
+    /// Returns the largest value the offsets can currently take.
+    #[inline]
+    pub(crate) fn max_offset(&self) -> usize {
+        self.indices
+            .iter()
+            .skip(1)
+            .map(|index| index.len() - 1)
+            .chain(self.indices.last().map(|_| self.buffer.len()))
+            .max()
+            .unwrap_or(0)
+    }
+    /// Checks that the offsets are consistent: every level starts at zero, never decreases
+    /// and ends at the number of entries in the next level, or in the buffer for the last level.
+    ///
+    /// Returns an error describing the first violation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// let data = jagged![[1, 2], [3]];
+    /// assert!(data.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), JaggedArrayError> {
+        validate_offsets(
+            self.indices.iter().map(|index| &index[..]),
+            self.buffer.len(),
+            true,
+        )
+    }
+    /// Converts the array to an index buffer type whose index type can represent every offset,
+    /// rebuilding the offsets and moving the buffer as is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new index type is narrower than the current one.
+    /// Use [`JaggedArray::try_convert_index`] to narrow the offsets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// let data = jagged![u16; [1, 2], [3]];
+    /// let data = data.convert_index::<Vec<u32>>();
+    /// assert!(data == jagged![u32; [1, 2], [3]]);
+    /// let data = jagged![[1, 2], [3]].convert_index::<Vec<u64>>();
+    /// assert!(data == jagged![u64; [1, 2], [3]]);
+    /// ```
+    pub fn convert_index<TOther>(self) -> JaggedArray<TVal, TOther, N>
+    where
+        TOther: VecLike,
+        TOther::TI: AsPrimitive<usize> + Num + 'static,
+        TBuffer::TI: AsPrimitive<TOther::TI>,
+    {
+        assert_widening::<TBuffer::TI, TOther::TI>();
+        JaggedArray {
+            indices: self
+                .indices
+                .into_iter()
+                .map(|index| index.into_iter().map(AsPrimitive::as_).collect())
+                .collect(),
+            buffer: self.buffer,
+        }
+    }
+    /// Converts the array to another index buffer type, rebuilding the offsets and moving the buffer as is.
+    ///
+    /// Returns [`JaggedArrayError::IndexOverflow`] along with the unchanged array
+    /// if an offset does not fit in the new index type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, JaggedArray, JaggedArrayError};
+    /// let data = jagged![u32; [1, 2], [3]];
+    /// let data = data.try_convert_index::<Vec<u8>>().unwrap();
+    /// assert!(data == jagged![u8; [1, 2], [3]]);
+    /// let data = JaggedArray::<u8, Vec<u16>, 2>::from(vec![vec![0; 256]]);
+    /// let (error, data) = data.try_convert_index::<Vec<u8>>().unwrap_err();
+    /// assert!(error == JaggedArrayError::IndexOverflow && data.buffer_len() == 256);
+    /// ```
+    pub fn try_convert_index<TOther>(
+        self,
+    ) -> Result<JaggedArray<TVal, TOther, N>, (JaggedArrayError, Self)>
+    where
+        TOther: VecLike,
+        TOther::TI: AsPrimitive<usize> + Num + TryFrom<TBuffer::TI>,
+    {
+        match convert_levels::<_, _, N>(self.indices.iter().map(|index| &index[..])) {
+            Ok(indices) => Ok(JaggedArray {
+                indices,
+                buffer: self.buffer,
+            }),
+            Err(error) => Err((error, self)),
+        }
+    }
+    /// Returns the index of the parent row at `path` in its level, if any,
+    /// along with the range its children occupy in the next level.
+    fn children_of(
+        &self,
+        path: &[usize],
+    ) -> Result<(Option<usize>, Range<usize>), JaggedArrayError> {
+        let mut children = 0..self
+            .indices
+            .first()
+            .map_or(self.buffer.len(), |index| index.len() - 1);
+        let mut parent = None;
+        for (level, &i) in path.iter().enumerate() {
+            if i >= children.len() {
+                return Err(JaggedArrayError::OutOfBounds);
+            }
+            let id = children.start + i;
+            children = self.indices[level][id].as_()..self.indices[level][id + 1].as_();
+            parent = Some(id);
+        }
+        Ok((parent, children))
+    }
+    fn drain_rows_in(
+        &mut self,
+        path: &[usize],
+        range: Range<usize>,
+        removed: impl FnMut(&[TBuffer::TI]),
+    ) -> Result<std::vec::Drain<'_, TVal>, JaggedArrayError> {
+        let (parent, children) = self.children_of(path)?;
+        if range.start > range.end || range.end > children.len() {
+            return Err(JaggedArrayError::OutOfBounds);
+        }
+        let range = children.start + range.start..children.start + range.end;
+        Ok(self.drain_range(path.len(), parent, range, removed))
+    }
+    /// Removes the rows in `range` of level `depth` belonging to `parent`.
+    /// `removed` is called with the offsets covering the removed rows in each level, from `depth` downwards.
+    fn drain_range(
+        &mut self,
+        depth: usize,
+        parent: Option<usize>,
+        mut range: Range<usize>,
+        mut removed: impl FnMut(&[TBuffer::TI]),
+    ) -> std::vec::Drain<'_, TVal> {
+        if let Some(parent) = parent {
+            for i in self.indices[depth - 1][parent + 1..].iter_mut() {
+                *i -= range.len().as_();
+            }
+        }
+        for index in self.indices.iter_mut().skip(depth) {
+            removed(&index[range.start..range.end + 1]);
+            let start = index[range.start];
+            let end = index[range.end];
+            index.remove_range(range.start + 1..range.end + 1);
+            for i in index[range.start + 1..].iter_mut() {
+                *i -= end - start;
+            }
+            range = start.as_()..end.as_();
+        }
+        self.buffer.drain(range)
+    }
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged_array::JaggedArray;
+    /// use crate::jaggedarray::jagged_array::JaggedArrayViewTrait;
+    /// let mut data = JaggedArray::<usize, Vec<u16>, 2>::new();
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[1,2,3]);
+    /// data.truncate::<0>(1);
+    /// assert!(data[[0,0]] == 1);
+    /// ```
+    pub fn truncate<const DIM: usize>(&mut self, row_length: usize) -> bool {
+        self.try_truncate::<DIM>(row_length).is_ok()
+    }
+    /// Same as [`JaggedArray::truncate`], but returns [`JaggedArrayError::OutOfBounds`]
+    /// instead of `false` if `row_length` is greater than the number of rows.
+    ///
+    /// For `DIM > 0` the rows of the outer dimensions are cut at the new end as well:
+    /// rows that start past it are removed and the last remaining one is shortened.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, JaggedArray, JaggedArrayError};
+    /// let mut data = jagged![u16; [1, 2], [3]];
+    /// assert!(data.try_truncate::<0>(3) == Err(JaggedArrayError::OutOfBounds));
+    /// assert!(data.try_truncate::<0>(1).is_ok());
+    /// assert!(data == jagged![u16; [1, 2]]);
+    /// ```
+    pub fn try_truncate<const DIM: usize>(
+        &mut self,
+        row_length: usize,
+    ) -> Result<(), JaggedArrayError> {
+        if row_length >= self.indices[DIM].len() {
+            return Err(JaggedArrayError::OutOfBounds);
+        }
+        let mut rows = row_length;
+        for index in self.indices[..DIM].iter_mut().rev() {
+            let kept = index[..]
+                .partition_point(|&x| x.as_() <= rows)
+                .min(index.len() - 1);
+            index.truncate(kept + 1);
+            let last = index.last_mut().unwrap();
+            if last.as_() > rows {
+                *last = rows.as_();
+            }
+            rows = kept;
+        }
+        self.indices[DIM].truncate(row_length + 1);
+        let mut end = self.indices[DIM][row_length];
+        for index in self.indices.iter_mut().skip(DIM + 1) {
+            index.truncate(end.as_() + 1);
+            end = *index.last().unwrap();
+        }
+        self.buffer.truncate(end.as_());
+        self.debug_check_ends();
+        Ok(())
+    }
+}
+
+/// # Example
+///
+/// ```
+/// use jaggedarray::jagged_array::JaggedArray;
+/// use crate::jaggedarray::jagged_array::JaggedArrayViewTrait;
+/// let data = [vec![1, 2], vec![], vec![3]]
+///     .into_iter()
+///     .collect::<JaggedArray<usize, Vec<u16>, 2>>();
+/// assert!(data.len() == 3);
+/// assert!(data[[0, 1]] == 2);
+/// assert!(data[[2, 0]] == 3);
+/// ```
+impl<TVal, TBuffer: VecLike, I> FromIterator<I> for JaggedArray<TVal, TBuffer, 2>
+where
+    I: IntoIterator<Item = TVal>,
+    <TBuffer as VecLike>::TI: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + std::cmp::PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: num::traits::AsPrimitive<<TBuffer as VecLike>::TI>,
+{
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let iter = iter.into_iter();
+        let mut result = Self::with_capacity([iter.size_hint().0, 0]);
+        for row in iter {
+            result.new_row::<0>();
+            result.extend_last_row(row.into_iter());
+        }
+        result
+    }
+}
+
+impl<TVal, TBuffer: VecLike, I> FromIterator<I> for JaggedArray<TVal, TBuffer, 3>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator<Item = TVal>,
+    <TBuffer as VecLike>::TI: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + std::cmp::PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: num::traits::AsPrimitive<<TBuffer as VecLike>::TI>,
+{
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let iter = iter.into_iter();
+        let mut result = Self::with_capacity([iter.size_hint().0, 0, 0]);
+        for row in iter {
+            let row = row.into_iter();
+            result.reserve([0, row.size_hint().0, 0]);
+            result.new_row::<0>();
+            for row in row {
+                result.new_row::<1>();
+                result.extend_last_row(row.into_iter());
+            }
+        }
+        result
+    }
+}
+
+impl<TVal, TBuffer: VecLike, I> FromIterator<I> for JaggedArray<TVal, TBuffer, 4>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::Item: IntoIterator<Item = TVal>,
+    <TBuffer as VecLike>::TI: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + std::cmp::PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: num::traits::AsPrimitive<<TBuffer as VecLike>::TI>,
+{
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let iter = iter.into_iter();
+        let mut result = Self::with_capacity([iter.size_hint().0, 0, 0, 0]);
+        for row in iter {
+            let row = row.into_iter();
+            result.reserve([0, row.size_hint().0, 0, 0]);
+            result.new_row::<0>();
+            for row in row {
+                let row = row.into_iter();
+                result.reserve([0, 0, row.size_hint().0, 0]);
+                result.new_row::<1>();
+                for row in row {
+                    result.new_row::<2>();
+                    result.extend_last_row(row.into_iter());
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<TVal, TBuffer: VecLike> From<Vec<Vec<TVal>>> for JaggedArray<TVal, TBuffer, 2>
+where
+    <TBuffer as VecLike>::TI: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + std::cmp::PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: num::traits::AsPrimitive<<TBuffer as VecLike>::TI>,
+{
+    fn from(value: Vec<Vec<TVal>>) -> Self {
+        let total = value.iter().map(Vec::len).sum();
+        let mut result = Self::with_capacity([value.len(), total]);
+        for row in value {
+            result.new_row::<0>();
+            result.extend_last_row(row.into_iter());
+        }
+        result
+    }
+}
+
+impl<TVal, TBuffer: VecLike> From<Vec<Vec<Vec<TVal>>>> for JaggedArray<TVal, TBuffer, 3>
+where
+    <TBuffer as VecLike>::TI: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + std::cmp::PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: num::traits::AsPrimitive<<TBuffer as VecLike>::TI>,
+{
+    fn from(value: Vec<Vec<Vec<TVal>>>) -> Self {
+        let rows = value.iter().map(Vec::len).sum();
+        let total = value.iter().flatten().map(Vec::len).sum();
+        let mut result = Self::with_capacity([value.len(), rows, total]);
+        for row in value {
+            result.new_row::<0>();
+            for row in row {
+                result.new_row::<1>();
+                result.extend_last_row(row.into_iter());
+            }
+        }
+        result
+    }
+}
+
+impl<TVal, TBuffer: VecLike> From<&[&[TVal]]> for JaggedArray<TVal, TBuffer, 2>
+where
+    TVal: Clone,
+    <TBuffer as VecLike>::TI: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + std::cmp::PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: num::traits::AsPrimitive<<TBuffer as VecLike>::TI>,
+{
+    fn from(value: &[&[TVal]]) -> Self {
+        let total = value.iter().map(|row| row.len()).sum();
+        let mut result = Self::with_capacity([value.len(), total]);
+        for row in value {
+            result.new_row::<0>();
+            result.extend_last_row_from_slice(row);
+        }
+        result
+    }
+}
+
+pub trait JaggedArrayViewTrait<TVal, TNum, const N: usize>: Index<[usize; N]>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    fn is_empty(&self) -> bool;
+    fn len(&self) -> usize;
+    fn view<const M: usize, const R: usize>(
+        &self,
+        index: [usize; M],
+    ) -> JaggedArrayView<'_, TVal, TNum, R>
+    where
+        U<N>: std::ops::Sub<U2> + std::ops::Sub<typenum::B1>,
+        <U<N> as std::ops::Sub<U2>>::Output: ArrayLength,
+        <U<N> as std::ops::Sub<typenum::B1>>::Output: std::ops::Sub<typenum::B1>,
+        <<U<N> as std::ops::Sub<typenum::B1>>::Output as std::ops::Sub<typenum::B1>>::Output:
+            ArrayLength,
+        U<N>: std::ops::Sub<U<M>>,
+        <U<N> as std::ops::Sub<U<M>>>::Output: IsEqual<U<R>>,
+        U<R>: std::ops::Sub<B1>,
+        <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+        <<U<N> as std::ops::Sub<U<M>>>::Output as IsEqual<U<R>>>::Output: NonZero,
+        Const<N>: ToUInt,
+        Const<M>: ToUInt,
+        Const<R>: ToUInt;
+    /// # Safety
+    ///
+    /// The caller must ensure that every value in `index` is within the respective bounds of the view
+    unsafe fn view_unchecked<const M: usize, const R: usize>(
+        &self,
+        index: [usize; M],
+    ) -> JaggedArrayView<'_, TVal, TNum, R>
+    where
+        U<N>: std::ops::Sub<U2> + std::ops::Sub<typenum::B1>,
+        <U<N> as std::ops::Sub<U2>>::Output: ArrayLength,
+        <U<N> as std::ops::Sub<typenum::B1>>::Output: std::ops::Sub<typenum::B1>,
+        <<U<N> as std::ops::Sub<typenum::B1>>::Output as std::ops::Sub<typenum::B1>>::Output:
+            ArrayLength,
+        U<N>: std::ops::Sub<U<M>>,
+        <U<N> as std::ops::Sub<U<M>>>::Output: IsEqual<U<R>>,
+        U<R>: std::ops::Sub<B1>,
+        <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+        <<U<N> as std::ops::Sub<U<M>>>::Output as IsEqual<U<R>>>::Output: NonZero,
+        Const<N>: ToUInt,
+        Const<M>: ToUInt,
+        Const<R>: ToUInt;
+    /// # Safety
+    ///
+    /// The caller must ensure that `index` is within the bounds of the view
+    unsafe fn get_unchecked(&self, index: [usize; N]) -> &TVal;
+    fn get(&self, index: [usize; N]) -> Option<&TVal>;
+    fn to_owned(self) -> JaggedArrayOwnedView<TVal, TNum, N>
+    where
+        TVal: Clone;
+    /// Returns an iterator over the outermost rows, each of them being a view of dimension `R`(`N - 1`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged_array::JaggedArray;
+    /// use crate::jaggedarray::jagged_array::JaggedArrayViewTrait;
+    /// let mut data = JaggedArray::<usize, Vec<u16>, 2>::new();
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[1, 2]);
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[3]);
+    /// let mut rows = data.rows::<1>();
+    /// assert!(rows.len() == 2);
+    /// assert!(rows.next_back().unwrap().as_slice() == [3]);
+    /// assert!(rows.next().unwrap().as_slice() == [1, 2]);
+    /// assert!(rows.next().is_none());
+    /// ```
+    fn rows<const R: usize>(&self) -> Rows<'_, TVal, TNum, R>
+    where
+        <U<N> as std::ops::Sub<B1>>::Output: IsEqual<U<R>>,
+        <<U<N> as std::ops::Sub<B1>>::Output as IsEqual<U<R>>>::Output: NonZero,
+        U<R>: std::ops::Sub<B1>,
+        <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+        Const<R>: ToUInt;
+    /// Returns an iterator over all elements in storage order.
+    fn iter(&self) -> std::slice::Iter<'_, TVal>;
+    /// Returns an iterator over all elements and their indices in storage order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged_array::JaggedArray;
+    /// use crate::jaggedarray::jagged_array::JaggedArrayViewTrait;
+    /// let mut data = JaggedArray::<usize, Vec<u16>, 2>::new();
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[1, 2]);
+    /// data.new_row::<0>();
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[3]);
+    /// let elements = data.iter_indexed().collect::<Vec<_>>();
+    /// assert!(elements == [([0, 0], &1), ([0, 1], &2), ([2, 0], &3)]);
+    /// ```
+    fn iter_indexed(&self) -> IndexedIter<'_, TVal, TNum, N>;
+    /// Divides the view into two at the outermost row `mid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// use jaggedarray::JaggedArrayViewTrait;
+    /// let data = jagged![[1, 2], [3], [4, 5]];
+    /// let (left, right) = data.split_at(1);
+    /// assert!(left.to_nested_vec() == vec![vec![1, 2]]);
+    /// assert!(right.to_nested_vec() == vec![vec![3], vec![4, 5]]);
+    /// ```
+    fn split_at(
+        &self,
+        mid: usize,
+    ) -> (
+        JaggedArrayView<'_, TVal, TNum, N>,
+        JaggedArrayView<'_, TVal, TNum, N>,
+    );
+    /// Returns the offsets of the rows of dimension `DIM` into the rows of dimension `DIM + 1`,
+    /// so that the children of row `i` lie in `offsets[i]..offsets[i + 1]`.
+    ///
+    /// The offsets of a view are a window into those of the viewed array, so they only start from zero
+    /// for [`JaggedArray`] and [`JaggedArrayOwnedView`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// use jaggedarray::JaggedArrayViewTrait;
+    /// let data = jagged![[[1, 2], [3]], [[4]]];
+    /// assert!(data.offsets::<0>() == [0, 2, 3]);
+    /// assert!(data.offsets::<1>() == [0, 2, 3, 4]);
+    /// assert!(data.view::<1, 2>([1]).offsets::<0>() == [3, 4]);
+    /// ```
+    fn offsets<const DIM: usize>(&self) -> &[TNum]
+    where
+        U<N>: std::ops::Sub<U<DIM>>,
+        Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output>: Unsigned + NonZero,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: std::ops::Sub<typenum::B1>,
+        Const<DIM>: ToUInt;
+    /// Returns an iterator over the number of children of every row of dimension `DIM`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// use jaggedarray::JaggedArrayViewTrait;
+    /// let data = jagged![[[1, 2], [3]], [[4]]];
+    /// assert!(data.row_lengths::<0>().eq([2, 1]));
+    /// assert!(data.row_lengths::<1>().eq([2, 1, 1]));
+    /// ```
+    #[inline]
+    fn row_lengths<const DIM: usize>(&self) -> RowLengths<'_, TNum>
+    where
+        U<N>: std::ops::Sub<U<DIM>>,
+        Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output>: Unsigned + NonZero,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: std::ops::Sub<typenum::B1>,
+        Const<DIM>: ToUInt,
+    {
+        RowLengths::new(self.offsets::<DIM>())
+    }
+    /// Returns the total number of rows of dimension `DIM`, or the number of elements if `DIM` is `N - 1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// use jaggedarray::JaggedArrayViewTrait;
+    /// let data = jagged![[[1, 2], [3]], [[4]]];
+    /// assert!(data.len_at::<0>() == 2);
+    /// assert!(data.len_at::<1>() == 3);
+    /// assert!(data.len_at::<2>() == 4);
+    /// ```
+    fn len_at<const DIM: usize>(&self) -> usize
+    where
+        U<N>: std::ops::Sub<U<DIM>>,
+        <U<N> as std::ops::Sub<U<DIM>>>::Output: NonZero,
+        Const<DIM>: ToUInt;
+    /// Returns a parallel iterator over the outermost rows, each of them being a view of dimension `R`(`N - 1`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged_array::JaggedArray;
+    /// use crate::jaggedarray::jagged_array::JaggedArrayViewTrait;
+    /// use rayon::prelude::*;
+    /// let mut data = JaggedArray::<usize, Vec<u16>, 2>::new();
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[1, 2]);
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[3]);
+    /// let sums = data.par_rows::<1>().map(|row| row.as_slice().iter().sum()).collect::<Vec<usize>>();
+    /// assert!(sums == [3, 3]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn par_rows<const R: usize>(&self) -> ParRows<'_, TVal, TNum, R>
+    where
+        TVal: Sync,
+        TNum: Sync,
+        <U<N> as std::ops::Sub<B1>>::Output: IsEqual<U<R>>,
+        <<U<N> as std::ops::Sub<B1>>::Output as IsEqual<U<R>>>::Output: NonZero,
+        U<R>: std::ops::Sub<B1>,
+        <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+        Const<R>: ToUInt,
+    {
+        ParRows::new(self.rows())
+    }
+    /// Returns a parallel iterator over all elements.
+    #[cfg(feature = "rayon")]
+    fn par_iter(&self) -> rayon::slice::Iter<'_, TVal>
+    where
+        TVal: Sync;
+    /// Returns the largest number of children of a row at every dimension,
+    /// which is the shape of the smallest dense array that can hold the view.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// use jaggedarray::JaggedArrayViewTrait;
+    /// let data = jagged![[[1, 2], [3]], [], [[4], [5], [6]]];
+    /// assert!(data.padded_shape() == [3, 3, 2]);
+    /// ```
+    fn padded_shape(&self) -> [usize; N];
+    /// Copies the view into a dense array of [`padded_shape`](JaggedArrayViewTrait::padded_shape),
+    /// filling the entries that are missing from the view with `fill`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// use jaggedarray::JaggedArrayViewTrait;
+    /// let data = jagged![[1, 2], [], [3]];
+    /// let array = data.to_padded_array(0);
+    /// assert!(array == ndarray::array![[1, 2], [0, 0], [3, 0]]);
+    /// ```
+    #[cfg(feature = "ndarray")]
+    fn to_padded_array(&self, fill: TVal) -> ndarray::Array<TVal, ndarray::Dim<[usize; N]>>
+    where
+        TVal: Clone,
+        [usize; N]: ndarray::IntoDimension<Dim = ndarray::Dim<[usize; N]>>,
+        ndarray::Dim<[usize; N]>: ndarray::Dimension,
+    {
+        let mut array = ndarray::Array::from_elem(self.padded_shape(), fill);
+        for (index, value) in self.iter_indexed() {
+            array[ndarray::Dim(index)] = value.clone();
+        }
+        array
+    }
+    /// Same as [`to_padded_array`](JaggedArrayViewTrait::to_padded_array),
+    /// but also returns a mask that is `true` for the entries present in the view.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// use jaggedarray::JaggedArrayViewTrait;
+    /// let data = jagged![[1, 2], [3]];
+    /// let (array, mask) = data.to_padded_array_with_mask(0);
+    /// assert!(array == ndarray::array![[1, 2], [3, 0]]);
+    /// assert!(mask == ndarray::array![[true, true], [true, false]]);
+    /// ```
+    #[cfg(feature = "ndarray")]
+    #[allow(clippy::type_complexity)]
+    fn to_padded_array_with_mask(
+        &self,
+        fill: TVal,
+    ) -> (
+        ndarray::Array<TVal, ndarray::Dim<[usize; N]>>,
+        ndarray::Array<bool, ndarray::Dim<[usize; N]>>,
+    )
+    where
+        TVal: Clone,
+        [usize; N]: ndarray::IntoDimension<Dim = ndarray::Dim<[usize; N]>>,
+        ndarray::Dim<[usize; N]>: ndarray::Dimension,
+    {
+        let array = self.to_padded_array(fill);
+        let mut mask = ndarray::Array::from_elem(array.raw_dim(), false);
+        for (index, _) in self.iter_indexed() {
+            mask[ndarray::Dim(index)] = true;
         }
+        (array, mask)
     }
 }
-pub trait JaggedArrayViewTrait<TVal, TNum, const N: usize>: Index<[usize; N]>
+
+pub trait JaggedArrayMutViewTrait<TVal, TNum, const N: usize>:
+    JaggedArrayViewTrait<TVal, TNum, N> + IndexMut<[usize; N]>
 where
     TNum: AsPrimitive<usize> + Num,
     U<N>: std::ops::Sub<B1>,
     <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
     Const<N>: ToUInt,
 {
-    fn is_empty(&self) -> bool;
-    fn len(&self) -> usize;
-    fn view<const M: usize, const R: usize>(
-        &self,
+    fn view_mut<const M: usize, const R: usize>(
+        &mut self,
         index: [usize; M],
-    ) -> JaggedArrayView<TVal, TNum, R>
+    ) -> JaggedArrayMutView<'_, TVal, TNum, R>
     where
         U<N>: std::ops::Sub<U2> + std::ops::Sub<typenum::B1>,
         <U<N> as std::ops::Sub<U2>>::Output: ArrayLength,
@@ -447,79 +1772,327 @@ where
         Const<N>: ToUInt,
         Const<M>: ToUInt,
         Const<R>: ToUInt;
-    /// # Safety
+    /// Returns an iterator over the outermost rows that yields disjoint mutable views of dimension `R`(`N - 1`).
     ///
-    /// The caller must ensure that every value in `index` is within the respective bounds of the view
-    unsafe fn view_unchecked<const M: usize, const R: usize>(
-        &self,
-        index: [usize; M],
-    ) -> JaggedArrayView<TVal, TNum, R>
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged_array::JaggedArray;
+    /// use crate::jaggedarray::jagged_array::JaggedArrayMutViewTrait;
+    /// let mut data = JaggedArray::<usize, Vec<u16>, 2>::new();
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[1, 2]);
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[3]);
+    /// let mut rows = data.rows_mut::<1>();
+    /// let mut first = rows.next().unwrap();
+    /// let mut second = rows.next().unwrap();
+    /// std::mem::swap(&mut first[[0]], &mut second[[0]]);
+    /// assert!(data[[0, 0]] == 3);
+    /// assert!(data[[1, 0]] == 1);
+    /// ```
+    fn rows_mut<const R: usize>(&mut self) -> RowsMut<'_, TVal, TNum, R>
     where
-        U<N>: std::ops::Sub<U2> + std::ops::Sub<typenum::B1>,
-        <U<N> as std::ops::Sub<U2>>::Output: ArrayLength,
-        <U<N> as std::ops::Sub<typenum::B1>>::Output: std::ops::Sub<typenum::B1>,
-        <<U<N> as std::ops::Sub<typenum::B1>>::Output as std::ops::Sub<typenum::B1>>::Output:
-            ArrayLength,
-        U<N>: std::ops::Sub<U<M>>,
-        <U<N> as std::ops::Sub<U<M>>>::Output: IsEqual<U<R>>,
+        <U<N> as std::ops::Sub<B1>>::Output: IsEqual<U<R>>,
+        <<U<N> as std::ops::Sub<B1>>::Output as IsEqual<U<R>>>::Output: NonZero,
         U<R>: std::ops::Sub<B1>,
         <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
-        <<U<N> as std::ops::Sub<U<M>>>::Output as IsEqual<U<R>>>::Output: NonZero,
-        Const<N>: ToUInt,
-        Const<M>: ToUInt,
         Const<R>: ToUInt;
-    /// # Safety
+    /// Returns a mutable iterator over all elements in storage order.
+    fn iter_mut(&mut self) -> std::slice::IterMut<'_, TVal>;
+    /// Returns a mutable iterator over all elements and their indices in storage order.
     ///
-    /// The caller must ensure that `index` is within the bounds of the view
-    unsafe fn get_unchecked(&self, index: [usize; N]) -> &TVal;
-    fn get(&self, index: [usize; N]) -> Option<&TVal>;
-    fn to_owned(self) -> JaggedArrayOwnedView<TVal, TNum, N>
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged_array::JaggedArray;
+    /// use crate::jaggedarray::jagged_array::JaggedArrayMutViewTrait;
+    /// let mut data = JaggedArray::<usize, Vec<u16>, 2>::new();
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[1, 2]);
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[3]);
+    /// for ([i, j], value) in data.iter_indexed_mut() {
+    ///     *value = i * 10 + j;
+    /// }
+    /// assert!(data[[0, 1]] == 1);
+    /// assert!(data[[1, 0]] == 10);
+    /// ```
+    fn iter_indexed_mut(&mut self) -> IndexedIterMut<'_, TVal, TNum, N>;
+    /// Divides the view into two disjoint mutable views at the outermost row `mid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged;
+    /// use jaggedarray::JaggedArrayMutViewTrait;
+    /// let mut data = jagged![[1, 2], [3], [4, 5]];
+    /// let (mut left, mut right) = data.split_at_mut(2);
+    /// std::mem::swap(&mut left[[1, 0]], &mut right[[0, 1]]);
+    /// assert!(data.to_nested_vec() == vec![vec![1, 2], vec![5], vec![4, 3]]);
+    /// ```
+    fn split_at_mut(
+        &mut self,
+        mid: usize,
+    ) -> (
+        JaggedArrayMutView<'_, TVal, TNum, N>,
+        JaggedArrayMutView<'_, TVal, TNum, N>,
+    );
+    /// Returns a parallel iterator over the outermost rows that yields disjoint mutable views of dimension `R`(`N - 1`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::jagged_array::JaggedArray;
+    /// use crate::jaggedarray::jagged_array::JaggedArrayMutViewTrait;
+    /// use rayon::prelude::*;
+    /// let mut data = JaggedArray::<usize, Vec<u16>, 2>::new();
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[1, 2]);
+    /// data.new_row::<0>();
+    /// data.extend_last_row_from_slice(&[3]);
+    /// data.par_rows_mut::<1>().for_each(|mut row| row[[0]] = 0);
+    /// assert!(data[[0, 0]] == 0);
+    /// assert!(data[[1, 0]] == 0);
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn par_rows_mut<const R: usize>(&mut self) -> ParRowsMut<'_, TVal, TNum, R>
     where
-        TVal: Clone;
+        TVal: Send,
+        TNum: Sync,
+        <U<N> as std::ops::Sub<B1>>::Output: IsEqual<U<R>>,
+        <<U<N> as std::ops::Sub<B1>>::Output as IsEqual<U<R>>>::Output: NonZero,
+        U<R>: std::ops::Sub<B1>,
+        <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+        Const<R>: ToUInt,
+    {
+        ParRowsMut::new(self.rows_mut())
+    }
 }
 
-pub trait JaggedArrayMutViewTrait<TVal, TNum, const N: usize>:
-    JaggedArrayViewTrait<TVal, TNum, N> + IndexMut<[usize; N]>
+pub trait JaggedArray1DViewTrait<TVal, TNum>
+where
+    TNum: AsPrimitive<usize> + Num,
+{
+    fn as_slice(&self) -> &[TVal];
+}
+pub trait JaggedArray1DMutViewTrait<TVal, TNum>
 where
     TNum: AsPrimitive<usize> + Num,
+{
+    fn as_slice_mut(&mut self) -> &mut [TVal];
+}
+/// Returns the first offset of `level`, which every offset in it is relative to.
+///
+/// Arrays that own their offsets always start them from zero, so they pass `based = false`
+/// and skip the load entirely. Only borrowed views may start elsewhere.
+#[inline(always)]
+fn first_offset<TNum: AsPrimitive<usize>>(level: &[TNum], based: bool) -> usize {
+    if based {
+        level[0].as_()
+    } else {
+        0
+    }
+}
+
+/// Same as [`first_offset`], without bounds checking.
+///
+/// # Safety
+///
+/// `level` must not be empty if `based` is true.
+#[inline(always)]
+unsafe fn first_offset_unchecked<TNum: AsPrimitive<usize>>(level: &[TNum], based: bool) -> usize {
+    if based {
+        (*level.get_unchecked(0)).as_()
+    } else {
+        0
+    }
+}
+
+/// Writes into `dst` the windows of `levels` that are covered by `offsets` and returns the covered range of the buffer.
+///
+/// `base` is the first offset of the level that `offsets` is a window of,
+/// and `based` is passed on to [`first_offset`] for the levels below it.
+#[inline]
+fn narrow<'a, 'b, TNum: AsPrimitive<usize>>(
+    offsets: &'a [TNum],
+    mut base: usize,
+    based: bool,
+    levels: impl Iterator<Item = &'a [TNum]>,
+    dst: impl Iterator<Item = &'b mut MaybeUninit<&'a [TNum]>>,
+) -> Range<usize>
+where
+    'a: 'b,
+{
+    let mut start = offsets[0].as_() - base;
+    let mut end = offsets[offsets.len() - 1].as_() - base;
+    for (level, dst) in zip(levels, dst) {
+        let window = &level[start..end + 1];
+        dst.write(window);
+        base = first_offset(level, based);
+        start = window[0].as_() - base;
+        end = window[window.len() - 1].as_() - base;
+    }
+    start..end
+}
+
+/// Converts `value` to an offset, failing if it does not fit in the index type.
+#[inline]
+pub(crate) fn to_index<TNum>(value: usize) -> Result<TNum, JaggedArrayError>
+where
+    TNum: AsPrimitive<usize>,
+    usize: AsPrimitive<TNum>,
+{
+    let index = value.as_();
+    if index.as_() == value {
+        Ok(index)
+    } else {
+        Err(JaggedArrayError::IndexOverflow)
+    }
+}
+
+/// Converts every level of offsets to another index buffer type, rebasing windows so that they start from zero.
+fn convert_levels<'a, TNum, TBuffer, const N: usize>(
+    levels: impl Iterator<Item = &'a [TNum]>,
+) -> Result<GenericArray<TBuffer, Sub1<U<N>>>, JaggedArrayError>
+where
+    TNum: Num + Copy + 'a,
+    TBuffer: VecLike,
+    TBuffer::TI: TryFrom<TNum>,
     U<N>: std::ops::Sub<B1>,
     <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
     Const<N>: ToUInt,
 {
-    fn view_mut<const M: usize, const R: usize>(
-        &mut self,
-        index: [usize; M],
-    ) -> JaggedArrayMutView<TVal, TNum, R>
-    where
-        U<N>: std::ops::Sub<U2> + std::ops::Sub<typenum::B1>,
-        <U<N> as std::ops::Sub<U2>>::Output: ArrayLength,
-        <U<N> as std::ops::Sub<typenum::B1>>::Output: std::ops::Sub<typenum::B1>,
-        <<U<N> as std::ops::Sub<typenum::B1>>::Output as std::ops::Sub<typenum::B1>>::Output:
-            ArrayLength,
-        U<N>: std::ops::Sub<U<M>>,
-        <U<N> as std::ops::Sub<U<M>>>::Output: IsEqual<U<R>>,
-        U<R>: std::ops::Sub<B1>,
-        <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
-        <<U<N> as std::ops::Sub<U<M>>>::Output as IsEqual<U<R>>>::Output: NonZero,
-        Const<N>: ToUInt,
-        Const<M>: ToUInt,
-        Const<R>: ToUInt;
+    levels
+        .map(|index| {
+            index
+                .iter()
+                .map(|&x| TBuffer::TI::try_from(x - index[0]))
+                .collect::<Result<TBuffer, _>>()
+                .map_err(|_| JaggedArrayError::IndexOverflow)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|levels| levels.into_iter().collect())
 }
 
-pub trait JaggedArray1DViewTrait<TVal, TNum>
+/// Panics if `TTo` is narrower than `TFrom`, so that offsets could be truncated when converted.
+#[inline]
+fn assert_widening<TFrom, TTo>() {
+    assert!(
+        std::mem::size_of::<TFrom>() <= std::mem::size_of::<TTo>(),
+        "{} offsets do not fit in {}",
+        std::any::type_name::<TFrom>(),
+        std::any::type_name::<TTo>()
+    );
+}
+
+/// Collects a window of offsets so that they start from zero.
+#[inline]
+fn rebase<TNum: Num + Copy, T: FromIterator<TNum>>(offsets: &[TNum]) -> T {
+    offsets.iter().map(|&x| x - offsets[0]).collect()
+}
+
+/// Checks every level of `levels` against the next one and the last one against `buffer_len`.
+///
+/// Levels that are windows into the offsets of another array may start anywhere unless `from_zero` is set.
+pub(crate) fn validate_offsets<'a, TNum: AsPrimitive<usize>>(
+    levels: impl Iterator<Item = &'a [TNum]> + Clone,
+    buffer_len: usize,
+    from_zero: bool,
+) -> Result<(), JaggedArrayError> {
+    let lengths = levels
+        .clone()
+        .skip(1)
+        .map(|index| index.len().checked_sub(1))
+        .chain(std::iter::once(Some(buffer_len)));
+    for (level, (index, expected)) in zip(levels, lengths).enumerate() {
+        let first = index
+            .first()
+            .ok_or(JaggedArrayError::MissingOffsets { level })?
+            .as_();
+        if from_zero && first != 0 {
+            return Err(JaggedArrayError::NonZeroStart {
+                level,
+                offset: first,
+            });
+        }
+        if let Some(position) = index
+            .windows(2)
+            .position(|pair| pair[1].as_() < pair[0].as_())
+        {
+            return Err(JaggedArrayError::DecreasingOffsets {
+                level,
+                position: position + 1,
+            });
+        }
+        let actual = index[index.len() - 1].as_() - first;
+        // An empty next level is reported when it is reached
+        if let Some(expected) = expected.filter(|&expected| expected != actual) {
+            return Err(JaggedArrayError::LengthMismatch {
+                level,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+impl<TVal, TNum, const N: usize> JaggedArrayOwnedView<TVal, TNum, N>
 where
     TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
 {
-    fn as_slice(&self) -> &[TVal];
+    /// See [`JaggedArray::validate`].
+    pub fn validate(&self) -> Result<(), JaggedArrayError> {
+        validate_offsets(
+            self.indices.iter().map(|index| &index[..]),
+            self.buffer.len(),
+            true,
+        )
+    }
 }
-pub trait JaggedArray1DMutViewTrait<TVal, TNum>
+
+impl<'a, TVal, TNum, const N: usize> JaggedArrayView<'a, TVal, TNum, N>
 where
     TNum: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
 {
-    fn as_slice_mut(&mut self) -> &mut [TVal];
+    /// Creates a view that borrows offsets and values from external memory,
+    /// one level of offsets per dimension but the last.
+    ///
+    /// A level may start at any offset, but it must never decrease and it must span
+    /// as many rows as the next level holds, or as many values as `buffer` holds for the last level.
+    /// Returns an error describing the first violation otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, GenericArray, JaggedArrayView, JaggedArrayViewTrait};
+    /// let offsets: &[u32] = &[4, 6, 7];
+    /// let view = JaggedArrayView::from_raw_parts(GenericArray::from([offsets]), &[1, 2, 3]).unwrap();
+    /// assert!(view.to_owned() == jagged![u32; [1, 2], [3]].view::<0, 2>([]).to_owned());
+    /// assert!(JaggedArrayView::<_, _, 2>::from_raw_parts(GenericArray::from([offsets]), &[1, 2]).is_err());
+    /// ```
+    pub fn from_raw_parts(
+        indices: GenericArray<&'a [TNum], Sub1<U<N>>>,
+        buffer: &'a [TVal],
+    ) -> Result<Self, JaggedArrayError> {
+        validate_offsets(indices.iter().copied(), buffer.len(), false)?;
+        Ok(Self { indices, buffer })
+    }
 }
+
 macro_rules! impl_view {
-    ($num:ty, $typ:ident< $( $gen:tt ),+>,$type1:ty,$type2:path) => {
+    ($num:ty, $typ:ident< $( $gen:tt ),+>,$type1:ty,$type2:path, $based:expr) => {
         impl<$( $gen ),+,const N:usize> JaggedArrayViewTrait<TVal, $num, N>
             for $typ<$($gen),+, N>
         where $type1:$type2,
@@ -541,11 +2114,33 @@ macro_rules! impl_view {
             fn is_empty(&self) -> bool {
                 self.buffer.is_empty()
             }
+            #[inline]
+            fn offsets<const DIM: usize>(&self) -> &[$num]
+            where
+                U<N>: std::ops::Sub<U<DIM>>,
+                Sub1<<U<N> as std::ops::Sub<U<DIM>>>::Output>: Unsigned + NonZero,
+                <U<N> as std::ops::Sub<U<DIM>>>::Output: std::ops::Sub<typenum::B1>,
+                Const<DIM>: ToUInt,
+            {
+                &self.indices[DIM]
+            }
+            #[inline]
+            fn len_at<const DIM: usize>(&self) -> usize
+            where
+                U<N>: std::ops::Sub<U<DIM>>,
+                <U<N> as std::ops::Sub<U<DIM>>>::Output: NonZero,
+                Const<DIM>: ToUInt,
+            {
+                match self.indices.get(DIM) {
+                    Some(index) => index.len() - 1,
+                    None => self.buffer.len(),
+                }
+            }
             /// Rust const generics does not support arithmetic, so we have to specify the view's dimension(R) as well
             fn view<const M: usize, const R: usize>(
                 &self,
                 index: [usize; M],
-            ) -> JaggedArrayView<TVal, $num, R>
+            ) -> JaggedArrayView<'_, TVal, $num, R>
             where
             U<N>: std::ops::Sub<U2> + std::ops::Sub<typenum::B1>,
             <U<N> as std::ops::Sub<U2>>::Output: ArrayLength,
@@ -564,24 +2159,30 @@ macro_rules! impl_view {
                 let m = (M+1).min(self.indices.len());
                 let (first,remaining) = self.indices.split_at(m);
                 let (index_buffer, self_indices) = first.split_first().unwrap();
+                let mut base = first_offset(index_buffer, $based);
                 let mut index_buffer = &index_buffer[..];
                 for (&i, idx) in zip(index.iter(), self_indices.iter()) {
-                    index_buffer = &idx[index_buffer[i].as_()..index_buffer[i + 1].as_() + 1]
+                    index_buffer = &idx[index_buffer[i].as_() - base..index_buffer[i + 1].as_() - base + 1];
+                    base = first_offset(idx, $based);
                 }
                 let mut result = GenericArray::<&[$num], Sub1<U<R>>>::uninit();
                 let (indices, buffer) = if R > 1 {
                     result[0].write(index_buffer);
-                    for (src,dst) in remaining.iter().zip(result.iter_mut().skip(1)) {
-                        dst.write(src);
-                    }
+                    let range = narrow(
+                        index_buffer,
+                        base,
+                        $based,
+                        remaining.iter().map(|idx| &idx[..]),
+                        result.iter_mut().skip(1),
+                    );
                     // SAFETY: Now safe as we initialized all elements from 0 to R-1
                     (
                         unsafe { GenericArray::assume_init(result) },
-                        &self.buffer[..],
+                        &self.buffer[range],
                     )
                 } else {
-                    let start_index = index_buffer[*index.last().unwrap()].as_();
-                    let end_index = index_buffer[*index.last().unwrap() + 1].as_();
+                    let start_index = index_buffer[*index.last().unwrap()].as_() - base;
+                    let end_index = index_buffer[*index.last().unwrap() + 1].as_() - base;
                     // SAFETY: zero-sized arrays don't need initialization
                     (
                         unsafe { GenericArray::assume_init(result) },
@@ -594,7 +2195,7 @@ macro_rules! impl_view {
             unsafe fn view_unchecked<const M: usize, const R: usize>(
                 &self,
                 index: [usize; M],
-            ) -> JaggedArrayView<TVal, $num, R>
+            ) -> JaggedArrayView<'_, TVal, $num, R>
             where
             U<N>: std::ops::Sub<U2> + std::ops::Sub<typenum::B1>,
             <U<N> as std::ops::Sub<U2>>::Output: ArrayLength,
@@ -611,26 +2212,37 @@ macro_rules! impl_view {
             Const<R>: ToUInt
             {
                 let mut index_buffer = self.indices.get_unchecked(0).get_unchecked(..);
+                let mut base = first_offset_unchecked(index_buffer, $based);
                 let m = (M+1).min(self.indices.len());
                 for i in 1..m {
-                    index_buffer = self.indices.get_unchecked(i).get_unchecked((*index_buffer.get_unchecked(*index.get_unchecked(i-1))).as_()
-                    ..(*index_buffer.get_unchecked(*index.get_unchecked(i-1)+1)+<$num>::ONE).as_());
+                    let idx = self.indices.get_unchecked(i);
+                    let row = *index.get_unchecked(i-1);
+                    index_buffer = idx.get_unchecked((*index_buffer.get_unchecked(row)).as_() - base
+                    ..(*index_buffer.get_unchecked(row+1)).as_() - base + 1);
+                    base = first_offset_unchecked(idx, $based);
                 }
                 let mut result = GenericArray::<&[$num], Sub1<U<R>>>::uninit();
                 let (indices, buffer) = if R > 1 {
                     result.get_unchecked_mut(0).write(index_buffer);
+                    let mut start = (*index_buffer.get_unchecked(0)).as_() - base;
+                    let mut end = (*index_buffer.get_unchecked(index_buffer.len()-1)).as_() - base;
                     for i in m..self.indices.len() {
-                        result.get_unchecked_mut(i-m+1).write(self.indices.get_unchecked(i));
+                        let idx = self.indices.get_unchecked(i);
+                        let window = idx.get_unchecked(start..end+1);
+                        result.get_unchecked_mut(i-m+1).write(window);
+                        base = first_offset_unchecked(idx, $based);
+                        start = (*window.get_unchecked(0)).as_() - base;
+                        end = (*window.get_unchecked(window.len()-1)).as_() - base;
                     }
                     // SAFETY: Now safe as we initialized all elements from 0 to R-1
                     (
                         unsafe { GenericArray::assume_init(result) },
-                        self.buffer.get_unchecked(..),
+                        self.buffer.get_unchecked(start..end),
                     )
                 } else {
                     let last = *index.get_unchecked(M-1);
-                    let start_index = (*index_buffer.get_unchecked(last)).as_();
-                    let end_index = (*index_buffer.get_unchecked(last+1)).as_();
+                    let start_index = (*index_buffer.get_unchecked(last)).as_() - base;
+                    let end_index = (*index_buffer.get_unchecked(last+1)).as_() - base;
                     // SAFETY: zero-sized arrays don't need initialization
                     (
                         unsafe { GenericArray::assume_init(result) },
@@ -641,15 +2253,18 @@ macro_rules! impl_view {
             }
             unsafe fn get_unchecked(&self, index: [usize; N]) -> &TVal {
                 if N > 1 {
-                    let mut buffer_ptr = self.indices.get_unchecked(0).get_unchecked(..).as_ptr();
+                    let first = self.indices.get_unchecked(0);
+                    let mut base = first_offset_unchecked(first, $based);
+                    let mut buffer_ptr = first.as_ptr();
                     for i in 1..N-1 {
                         let idx = self.indices.get_unchecked(i);
-                        let id = (*index.get_unchecked(i-1));
+                        let id = *index.get_unchecked(i-1);
                         let s = *buffer_ptr.add(id);
-                        buffer_ptr = idx.as_ptr().add(s.as_());
+                        buffer_ptr = idx.as_ptr().add(s.as_() - base);
+                        base = first_offset_unchecked(idx, $based);
                     }
                     let last = *index.get_unchecked(N - 2);
-                    let start_index = (*buffer_ptr.add(last)).as_();
+                    let start_index = (*buffer_ptr.add(last)).as_() - base;
                     self.buffer.get_unchecked(start_index+*index.get_unchecked(N - 1))
                 } else {
                     self.buffer.get_unchecked(*index.get_unchecked(0))
@@ -659,12 +2274,14 @@ macro_rules! impl_view {
             fn get(&self, index:[usize;N])->Option<&TVal>{
                 if N > 1 {
                     let mut buffer = &self.indices[0][..];
+                    let mut base = first_offset(buffer, $based);
                     for (&i, idx) in zip(index.iter(), self.indices[1..].iter()) {
-                        buffer = &idx.get(buffer.get(i)?.as_()..buffer.get(i + 1)?.as_() + 1)?
+                        buffer = idx.get(buffer.get(i)?.as_() - base..buffer.get(i + 1)?.as_() - base + 1)?;
+                        base = first_offset(idx, $based);
                     }
                     let last = index[index.len() - 2];
-                    let start_index = buffer.get(last)?.as_();
-                    let end_index = buffer.get(last + 1)?.as_();
+                    let start_index = buffer.get(last)?.as_() - base;
+                    let end_index = buffer.get(last + 1)?.as_() - base;
                     self.buffer.get(start_index..end_index)?.get(index[index.len() - 1])
                 } else {
                     self.buffer.get(index[0])
@@ -672,10 +2289,71 @@ macro_rules! impl_view {
             }
 
             fn to_owned(self) -> JaggedArrayOwnedView<TVal, $num, N> where TVal:Clone {
-                let indices = self.indices.iter().map(|idx| idx.to_vec().into_boxed_slice()).collect();
+                let indices = self.indices.iter().map(|idx| {
+                    let base = idx[0];
+                    idx.iter().map(|&x| x - base).collect()
+                }).collect();
                 let buffer = self.buffer.to_vec().into_boxed_slice();
                 JaggedArrayOwnedView { indices, buffer }
             }
+
+            fn rows<const R: usize>(&self) -> Rows<'_, TVal, $num, R>
+            where
+            <U<N> as std::ops::Sub<B1>>::Output: IsEqual<U<R>>,
+            <<U<N> as std::ops::Sub<B1>>::Output as IsEqual<U<R>>>::Output: NonZero,
+            U<R>: std::ops::Sub<B1>,
+            <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+            Const<R>: ToUInt
+            {
+                let (offsets, remaining) = self.indices.split_first().unwrap();
+                Rows::new(
+                    offsets,
+                    GenericArray::generate(|i| &remaining[i][..]),
+                    &self.buffer[..],
+                )
+            }
+
+            #[inline]
+            fn iter(&self) -> std::slice::Iter<'_, TVal> {
+                self.buffer.iter()
+            }
+
+            #[inline]
+            fn iter_indexed(&self) -> IndexedIter<'_, TVal, $num, N> {
+                IndexedIter::new(GenericArray::generate(|i| &self.indices[i][..]), &self.buffer[..])
+            }
+
+            #[inline]
+            fn split_at(&self, mid: usize) -> (JaggedArrayView<'_, TVal, $num, N>, JaggedArrayView<'_, TVal, $num, N>) {
+                let (left, right, buffer_mid) =
+                    split_levels(&GenericArray::generate(|i| &self.indices[i][..]), mid);
+                let (left_buffer, right_buffer) = self.buffer.split_at(buffer_mid);
+                (
+                    JaggedArrayView { indices: left, buffer: left_buffer },
+                    JaggedArrayView { indices: right, buffer: right_buffer },
+                )
+            }
+
+            #[cfg(feature = "rayon")]
+            #[inline]
+            fn par_iter(&self) -> rayon::slice::Iter<'_, TVal>
+            where
+                TVal: Sync,
+            {
+                rayon::prelude::IntoParallelIterator::into_par_iter(&self.buffer[..])
+            }
+
+            fn padded_shape(&self) -> [usize; N] {
+                let mut shape = [self.len(); N];
+                for (dim, index) in zip(shape[1..].iter_mut(), self.indices.iter()) {
+                    *dim = index
+                        .windows(2)
+                        .map(|pair| pair[1].as_() - pair[0].as_())
+                        .max()
+                        .unwrap_or(0);
+                }
+                shape
+            }
         }
 
         impl<$( $gen ),+,const N:usize> Index<[usize; N]> for $typ<$($gen),+, N>
@@ -690,12 +2368,14 @@ macro_rules! impl_view {
             fn index(&self, index: [usize; N]) -> &Self::Output {
                 if N > 1 {
                     let mut buffer = &self.indices[0][..];
+                    let mut base = first_offset(buffer, $based);
                     for (&i, idx) in zip(index.iter(), self.indices[1..].iter()) {
-                        buffer = &idx[buffer[i].as_()..buffer[i + 1].as_() + 1]
+                        buffer = &idx[buffer[i].as_() - base..buffer[i + 1].as_() - base + 1];
+                        base = first_offset(idx, $based);
                     }
                     let last = index[index.len() - 2];
-                    let start_index = buffer[last].as_();
-                    let end_index = buffer[last + 1].as_();
+                    let start_index = buffer[last].as_() - base;
+                    let end_index = buffer[last + 1].as_() - base;
                     &self.buffer[start_index..end_index][index[index.len() - 1]]
                 } else {
                     &self.buffer[index[0]]
@@ -706,7 +2386,7 @@ macro_rules! impl_view {
 }
 
 macro_rules! impl_view_mut {
-    ($num:ty, $typ:ident< $( $gen:tt ),+>,$type1:ty,$type2:path) => {
+    ($num:ty, $typ:ident< $( $gen:tt ),+>,$type1:ty,$type2:path, $based:expr) => {
         impl<$( $gen ),+,const N:usize> JaggedArrayMutViewTrait<TVal, $num, N>
             for $typ<$($gen),+, N>
         where $type1:$type2,
@@ -720,7 +2400,7 @@ macro_rules! impl_view_mut {
             fn view_mut<const M: usize, const R: usize>(
                 &mut self,
                 index: [usize; M],
-            ) -> JaggedArrayMutView<TVal, $num, R>
+            ) -> JaggedArrayMutView<'_, TVal, $num, R>
             where
             U<N>: std::ops::Sub<U2> + std::ops::Sub<typenum::B1>,
             <U<N> as std::ops::Sub<U2>>::Output: ArrayLength,
@@ -737,26 +2417,32 @@ macro_rules! impl_view_mut {
             Const<R>: ToUInt
             {
                 let m = (M+1).min(self.indices.len());
-                let (first,remaining) = self.indices.split_at_mut(m);
-                let (index_buffer, self_indices) = first.split_first_mut().unwrap();
-                let mut index_buffer = &mut index_buffer[..];
-                for (&i, idx) in zip(index.iter(), self_indices.iter_mut()) {
-                    index_buffer = &mut idx[index_buffer[i].as_()..index_buffer[i + 1].as_() + 1]
+                let (first,remaining) = self.indices.split_at(m);
+                let (index_buffer, self_indices) = first.split_first().unwrap();
+                let mut base = first_offset(index_buffer, $based);
+                let mut index_buffer = &index_buffer[..];
+                for (&i, idx) in zip(index.iter(), self_indices.iter()) {
+                    index_buffer = &idx[index_buffer[i].as_() - base..index_buffer[i + 1].as_() - base + 1];
+                    base = first_offset(idx, $based);
                 }
-                let mut result = GenericArray::<&mut [$num], Sub1<U<R>>>::uninit();
+                let mut result = GenericArray::<&[$num], Sub1<U<R>>>::uninit();
                 let (indices, buffer) = if R > 1 {
                     result[0].write(index_buffer);
-                    for (src,dst) in remaining.iter_mut().zip(result.iter_mut().skip(1)) {
-                        dst.write(src);
-                    }
+                    let range = narrow(
+                        index_buffer,
+                        base,
+                        $based,
+                        remaining.iter().map(|idx| &idx[..]),
+                        result.iter_mut().skip(1),
+                    );
                     // SAFETY: Now safe as we initialized all elements from 0 to R-1
                     (
                         unsafe { GenericArray::assume_init(result) },
-                        &mut self.buffer[..],
+                        &mut self.buffer[range],
                     )
                 } else {
-                    let start_index = index_buffer[*index.last().unwrap()].as_();
-                    let end_index = index_buffer[*index.last().unwrap() + 1].as_();
+                    let start_index = index_buffer[*index.last().unwrap()].as_() - base;
+                    let end_index = index_buffer[*index.last().unwrap() + 1].as_() - base;
                     // SAFETY: zero-sized arrays don't need initialization
                     (
                         unsafe { GenericArray::assume_init(result) },
@@ -765,6 +2451,45 @@ macro_rules! impl_view_mut {
                 };
                 JaggedArrayMutView { indices, buffer }
             }
+
+            fn rows_mut<const R: usize>(&mut self) -> RowsMut<'_, TVal, $num, R>
+            where
+            <U<N> as std::ops::Sub<B1>>::Output: IsEqual<U<R>>,
+            <<U<N> as std::ops::Sub<B1>>::Output as IsEqual<U<R>>>::Output: NonZero,
+            U<R>: std::ops::Sub<B1>,
+            <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+            Const<R>: ToUInt
+            {
+                let (offsets, remaining) = self.indices.split_first().unwrap();
+                RowsMut::new(
+                    offsets,
+                    GenericArray::generate(|i| &remaining[i][..]),
+                    &mut self.buffer[..],
+                )
+            }
+
+            #[inline]
+            fn iter_mut(&mut self) -> std::slice::IterMut<'_, TVal> {
+                self.buffer.iter_mut()
+            }
+
+            #[inline]
+            fn iter_indexed_mut(&mut self) -> IndexedIterMut<'_, TVal, $num, N> {
+                let indices = &self.indices;
+                IndexedIterMut::new(GenericArray::generate(|i| &indices[i][..]), &mut self.buffer[..])
+            }
+
+            #[inline]
+            fn split_at_mut(&mut self, mid: usize) -> (JaggedArrayMutView<'_, TVal, $num, N>, JaggedArrayMutView<'_, TVal, $num, N>) {
+                let indices = &self.indices;
+                let (left, right, buffer_mid) =
+                    split_levels(&GenericArray::generate(|i| &indices[i][..]), mid);
+                let (left_buffer, right_buffer) = self.buffer.split_at_mut(buffer_mid);
+                (
+                    JaggedArrayMutView { indices: left, buffer: left_buffer },
+                    JaggedArrayMutView { indices: right, buffer: right_buffer },
+                )
+            }
         }
 
         impl<$( $gen ),+,const N:usize> IndexMut<[usize; N]> for $typ<$($gen),+, N>
@@ -777,11 +2502,13 @@ macro_rules! impl_view_mut {
             fn index_mut(&mut self, index: [usize; N]) -> &mut TVal {
                 if N > 1 {
                     let mut buffer = &self.indices[0][..];
+                    let mut base = first_offset(buffer, $based);
                     for (&i, idx) in zip(index.iter(), self.indices[1..].iter()) {
-                        buffer = &idx[buffer[i].as_()..buffer[i + 1].as_() + 1]
+                        buffer = &idx[buffer[i].as_() - base..buffer[i + 1].as_() - base + 1];
+                        base = first_offset(idx, $based);
                     }
-                    let start_index = buffer[index[index.len() - 2]].as_();
-                    let end_index = buffer[index[index.len() - 2] + 1].as_();
+                    let start_index = buffer[index[index.len() - 2]].as_() - base;
+                    let end_index = buffer[index[index.len() - 2] + 1].as_() - base;
                     &mut self.buffer[start_index..end_index][index[index.len() - 1]]
                 } else {
                     &mut self.buffer[index[0]]
@@ -813,8 +2540,7 @@ macro_rules! impl_view_mut1d_owned {
 
 impl<'a, TVal, TNum> JaggedArrayView<'a, TVal, TNum, 1>
 where
-    TNum: AsPrimitive<usize> + Num + NumAssignOps + std::cmp::PartialOrd + ConstOne + ConstZero,
-    usize: num::traits::AsPrimitive<TNum>,
+    TNum: AsPrimitive<usize> + Num,
 {
     pub fn as_slice(&self) -> &'a [TVal] {
         self.buffer
@@ -823,7 +2549,13 @@ where
 
 impl<'a, TVal, TNum> JaggedArrayMutView<'a, TVal, TNum, 1>
 where
-    TNum: AsPrimitive<usize> + Num + NumAssignOps + std::cmp::PartialOrd + ConstOne + ConstZero,
+    TNum: AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + std::cmp::PartialOrd
+        + ConstOne
+        + ConstZero,
     usize: num::traits::AsPrimitive<TNum>,
 {
     pub fn as_slice<'b: 'a>(&'b self) -> &'a [TVal] {
@@ -836,13 +2568,188 @@ where
     }
 }
 
-impl_view!(<TBuffer as VecLike>::TI,JaggedArray<TVal, TBuffer>,TBuffer,VecLike);
-impl_view!(TNum, JaggedArrayView<'a, TVal, TNum>, TNum, Num);
-impl_view!(TNum, JaggedArrayMutView<'a, TVal, TNum>, TNum, Num);
-impl_view!(TNum,JaggedArrayOwnedView<TVal, TNum>,TNum,Num);
+/// Groups `values` into rows according to `offsets`.
+fn nested_vec_2d<TNum: AsPrimitive<usize>, TVal>(
+    offsets: &[TNum],
+    values: &mut impl Iterator<Item = TVal>,
+) -> Vec<Vec<TVal>> {
+    offsets
+        .windows(2)
+        .map(|w| values.take(w[1].as_() - w[0].as_()).collect())
+        .collect()
+}
+
+/// Groups `values` into rows of rows according to `outer` and `inner` offsets.
+fn nested_vec_3d<TNum: AsPrimitive<usize>, TVal>(
+    outer: &[TNum],
+    inner: &[TNum],
+    values: &mut impl Iterator<Item = TVal>,
+) -> Vec<Vec<Vec<TVal>>> {
+    let base = outer[0].as_();
+    outer
+        .windows(2)
+        .map(|w| nested_vec_2d(&inner[w[0].as_() - base..w[1].as_() - base + 1], values))
+        .collect()
+}
+
+macro_rules! impl_to_nested_vec {
+    ($num:ty, $typ:ident< $( $gen:tt ),+>,$type1:ty,$type2:path) => {
+        impl<$( $gen ),+> $typ<$($gen),+, 2> where $num: AsPrimitive<usize> + Num,$type1:$type2
+        {
+            /// # Example
+            ///
+            /// ```
+            /// use jaggedarray::jagged_array::JaggedArray;
+            /// let data = JaggedArray::<usize, Vec<u16>, 2>::from(vec![vec![1, 2], vec![], vec![3]]);
+            /// assert!(data.to_nested_vec() == vec![vec![1, 2], vec![], vec![3]]);
+            /// ```
+            pub fn to_nested_vec(&self) -> Vec<Vec<TVal>>
+            where
+                TVal: Clone,
+            {
+                nested_vec_2d(&self.indices[0], &mut self.buffer.iter().cloned())
+            }
+        }
+
+        impl<$( $gen ),+> $typ<$($gen),+, 3> where $num: AsPrimitive<usize> + Num,$type1:$type2
+        {
+            /// # Example
+            ///
+            /// ```
+            /// use jaggedarray::jagged_array::JaggedArray;
+            /// let data = JaggedArray::<usize, Vec<u16>, 3>::from(vec![vec![vec![1], vec![2, 3]], vec![]]);
+            /// assert!(data.to_nested_vec() == vec![vec![vec![1], vec![2, 3]], vec![]]);
+            /// ```
+            pub fn to_nested_vec(&self) -> Vec<Vec<Vec<TVal>>>
+            where
+                TVal: Clone,
+            {
+                nested_vec_3d(&self.indices[0], &self.indices[1], &mut self.buffer.iter().cloned())
+            }
+        }
+    };
+}
+
+macro_rules! impl_convert_index {
+    ($num:ty, $typ:ident< $( $gen:tt ),+>,$type1:ty,$type2:path) => {
+        impl<$( $gen ),+, const N: usize> $typ<$($gen),+, N>
+        where
+            $num: AsPrimitive<usize> + Num,
+            $type1: $type2,
+            U<N>: std::ops::Sub<B1>,
+            <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+            Const<N>: ToUInt,
+        {
+            /// Copies the view into a new array with another index buffer type
+            /// whose index type can represent every offset.
+            ///
+            /// # Example
+            ///
+            /// ```
+            /// use jaggedarray::{jagged, JaggedArrayViewTrait};
+            /// let data = jagged![u16; [[1], [2, 3]], [[4]]];
+            /// let row = data.view::<1, 2>([1]).convert_index::<Vec<u64>>();
+            /// assert!(row == jagged![u64; [4]]);
+            /// ```
+            pub fn convert_index<TBuffer>(&self) -> JaggedArray<TVal, TBuffer, N>
+            where
+                TVal: Clone,
+                TBuffer: VecLike,
+                TBuffer::TI: AsPrimitive<usize> + Num + 'static,
+                $num: AsPrimitive<TBuffer::TI>,
+            {
+                assert_widening::<$num, TBuffer::TI>();
+                JaggedArray {
+                    indices: self
+                        .indices
+                        .iter()
+                        .map(|index| index.iter().map(|&x| (x - index[0]).as_()).collect())
+                        .collect(),
+                    buffer: self.buffer.to_vec(),
+                }
+            }
+            /// Copies the view into a new array with another index buffer type.
+            ///
+            /// Returns an error if an offset does not fit in the new index type.
+            ///
+            /// # Example
+            ///
+            /// ```
+            /// use jaggedarray::{jagged, JaggedArray, JaggedArrayError, JaggedArrayViewTrait};
+            /// let data = JaggedArray::<u8, Vec<u16>, 2>::from(vec![vec![0; 256], vec![1]]);
+            /// let row = data.view::<0, 2>([]);
+            /// assert!(row.try_convert_index::<Vec<u8>>() == Err(JaggedArrayError::IndexOverflow));
+            /// let (_, right) = row.split_at(1);
+            /// assert!(right.try_convert_index::<Vec<u8>>().unwrap() == JaggedArray::from(vec![vec![1]]));
+            /// ```
+            pub fn try_convert_index<TBuffer>(&self) -> Result<JaggedArray<TVal, TBuffer, N>, JaggedArrayError>
+            where
+                TVal: Clone,
+                TBuffer: VecLike,
+                TBuffer::TI: AsPrimitive<usize> + Num + TryFrom<$num>,
+            {
+                Ok(JaggedArray {
+                    indices: convert_levels::<_, _, N>(self.indices.iter().map(|index| &index[..]))?,
+                    buffer: self.buffer.to_vec(),
+                })
+            }
+        }
+    };
+}
+
+macro_rules! impl_into_nested_vec {
+    ($num:ty, $typ:ident< $( $gen:tt ),+>,$type1:ty,$type2:path) => {
+        impl<$( $gen ),+> $typ<$($gen),+, 2> where $num: AsPrimitive<usize> + Num,$type1:$type2
+        {
+            /// Converts the array into nested vectors, moving the values out of the buffer.
+            ///
+            /// # Example
+            ///
+            /// ```
+            /// use jaggedarray::jagged_array::JaggedArray;
+            /// let data = JaggedArray::<String, Vec<u16>, 2>::from(vec![vec!["a".to_string()], vec![]]);
+            /// assert!(data.into_nested_vec() == vec![vec!["a".to_string()], vec![]]);
+            /// ```
+            pub fn into_nested_vec(self) -> Vec<Vec<TVal>> {
+                let Self { indices, buffer } = self;
+                nested_vec_2d(&indices[0], &mut Vec::from(buffer).into_iter())
+            }
+        }
+
+        impl<$( $gen ),+> $typ<$($gen),+, 3> where $num: AsPrimitive<usize> + Num,$type1:$type2
+        {
+            /// Converts the array into nested vectors, moving the values out of the buffer.
+            ///
+            /// # Example
+            ///
+            /// ```
+            /// use jaggedarray::jagged_array::JaggedArray;
+            /// let data = JaggedArray::<String, Vec<u16>, 3>::from(vec![vec![vec!["a".to_string()]]]);
+            /// assert!(data.into_nested_vec() == vec![vec![vec!["a".to_string()]]]);
+            /// ```
+            pub fn into_nested_vec(self) -> Vec<Vec<Vec<TVal>>> {
+                let Self { indices, buffer } = self;
+                nested_vec_3d(&indices[0], &indices[1], &mut Vec::from(buffer).into_iter())
+            }
+        }
+    };
+}
+
+impl_view!(<TBuffer as VecLike>::TI,JaggedArray<TVal, TBuffer>,TBuffer,VecLike,false);
+impl_view!(TNum, JaggedArrayView<'a, TVal, TNum>, TNum, Num, true);
+impl_view!(TNum, JaggedArrayMutView<'a, TVal, TNum>, TNum, Num, true);
+impl_view!(TNum,JaggedArrayOwnedView<TVal, TNum>,TNum,Num,false);
 impl_view1d_owned!(<TBuffer as VecLike>::TI,JaggedArray<TVal, TBuffer>,TBuffer,VecLike);
 impl_view1d_owned!(TNum,JaggedArrayOwnedView<TVal, TNum>,TNum,Num);
-impl_view_mut!(<TBuffer as VecLike>::TI,JaggedArray<TVal, TBuffer>,TBuffer,VecLike);
-impl_view_mut!(TNum, JaggedArrayMutView<'a, TVal, TNum>, TNum, Num);
-impl_view_mut!(TNum,JaggedArrayOwnedView<TVal, TNum>,TNum,Num);
+impl_view_mut!(<TBuffer as VecLike>::TI,JaggedArray<TVal, TBuffer>,TBuffer,VecLike,false);
+impl_view_mut!(TNum, JaggedArrayMutView<'a, TVal, TNum>, TNum, Num, true);
+impl_view_mut!(TNum,JaggedArrayOwnedView<TVal, TNum>,TNum,Num,false);
 impl_view_mut1d_owned!(TNum,JaggedArrayOwnedView<TVal, TNum>,TNum,Num);
+impl_to_nested_vec!(<TBuffer as VecLike>::TI,JaggedArray<TVal, TBuffer>,TBuffer,VecLike);
+impl_to_nested_vec!(TNum, JaggedArrayView<'a, TVal, TNum>, TNum, Num);
+impl_to_nested_vec!(TNum,JaggedArrayOwnedView<TVal, TNum>,TNum,Num);
+impl_into_nested_vec!(<TBuffer as VecLike>::TI,JaggedArray<TVal, TBuffer>,TBuffer,VecLike);
+impl_into_nested_vec!(TNum,JaggedArrayOwnedView<TVal, TNum>,TNum,Num);
+impl_convert_index!(TNum, JaggedArrayView<'a, TVal, TNum>, TNum, Num);
+impl_convert_index!(TNum, JaggedArrayMutView<'a, TVal, TNum>, TNum, Num);
+impl_convert_index!(TNum,JaggedArrayOwnedView<TVal, TNum>,TNum,Num);
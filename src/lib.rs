@@ -1,12 +1,33 @@
+pub mod adaptive;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "bytemuck")]
+pub mod binary;
+pub mod csr;
+pub mod dynamic;
+pub mod error;
+pub mod iter;
 pub mod jagged_array;
+mod macros;
+#[cfg(feature = "ndarray")]
+mod ndarray_impl;
+#[cfg(feature = "rayon")]
+pub mod par_iter;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod vec_ext;
 pub mod vec_like;
 
+pub use crate::adaptive::AdaptiveJaggedArray;
+pub use crate::dynamic::JaggedArrayDyn;
+pub use crate::error::JaggedArrayError;
 pub use crate::jagged_array::JaggedArray;
 pub use crate::jagged_array::JaggedArray1DMutViewTrait;
 pub use crate::jagged_array::JaggedArray1DViewTrait;
 pub use crate::jagged_array::JaggedArrayMutViewTrait;
+pub use crate::jagged_array::JaggedArrayView;
 pub use crate::jagged_array::JaggedArrayViewTrait;
+pub use generic_array::GenericArray;
 #[cfg(test)]
 mod tests {
     use jagged_array::JaggedArrayMutViewTrait;
@@ -138,6 +159,797 @@ mod tests {
         assert!(data[[0, 2, 1]] == 8);
         assert!(data[[0, 2, 2]] == 9);
     }
+    #[test]
+    fn rows_test() {
+        let mut data = jagged_array::JaggedArray::<i32, Vec<u16>, 3>::new();
+        data.new_row::<0>();
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[1, 2]);
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[3]);
+        data.new_row::<0>();
+        data.new_row::<0>();
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[4, 5, 6]);
+        let rows = data.rows::<2>();
+        assert!(rows.len() == 3);
+        let lengths = rows.map(|row| row.len()).collect::<Vec<_>>();
+        assert!(lengths == [2, 0, 1]);
+        let mut rows = data.rows::<2>();
+        let last = rows.next_back().unwrap();
+        assert!(last[[0, 2]] == 6);
+        assert!(rows.len() == 2);
+        let first = rows.next().unwrap();
+        let inner = first.rows::<1>().map(|row| row.as_slice()).collect::<Vec<_>>();
+        assert!(inner == [&[1, 2][..], &[3][..]]);
+        assert!(rows.next().map(|row| row.len()) == Some(0));
+        assert!(rows.next().is_none());
+        assert!(rows.next_back().is_none());
+        let view = data.view::<1, 2>([2]);
+        assert!(view.rows::<1>().flat_map(|row| row.as_slice()).sum::<i32>() == 15);
+        let owned = data.view::<0, 3>([]).to_owned();
+        assert!(owned.rows::<2>().rev().map(|row| row.len()).collect::<Vec<_>>() == [1, 0, 2]);
+    }
+
+    #[test]
+    fn rows_mut_test() {
+        let mut data = jagged_array::JaggedArray::<i32, Vec<u16>, 3>::new();
+        data.new_row::<0>();
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[1, 2]);
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[3]);
+        data.new_row::<0>();
+        data.new_row::<0>();
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[4, 5, 6]);
+        let mut rows = data.rows_mut::<2>();
+        let mut last = rows.next_back().unwrap();
+        let mut first = rows.next().unwrap();
+        std::mem::swap(&mut first[[1, 0]], &mut last[[0, 2]]);
+        for mut row in first.rows_mut::<1>() {
+            for i in 0..row.len() {
+                row[[i]] *= 10;
+            }
+        }
+        assert!(rows.next().map(|row| row.is_empty()) == Some(true));
+        assert!(rows.next().is_none());
+        assert!(data.view::<1, 2>([0]).view::<1, 1>([0]).as_slice() == [10, 20]);
+        assert!(data.view::<1, 2>([0]).view::<1, 1>([1]).as_slice() == [60]);
+        assert!(data.view::<1, 2>([2]).view::<1, 1>([0]).as_slice() == [4, 5, 3]);
+        let mut view = data.view_mut::<1, 2>([2]);
+        for mut row in view.rows_mut::<1>() {
+            row[[0]] = 0;
+        }
+        assert!(data[[2, 0, 0]] == 0);
+    }
+
+    #[test]
+    fn nested_view_test() {
+        let mut data = jagged_array::JaggedArray::<i32, Vec<u16>, 4>::new();
+        data.new_row::<0>();
+        data.new_row::<1>();
+        data.new_row::<2>();
+        data.extend_last_row_from_slice(&[1, 2]);
+        data.new_row::<0>();
+        data.new_row::<1>();
+        data.new_row::<2>();
+        data.extend_last_row_from_slice(&[3]);
+        data.new_row::<1>();
+        data.new_row::<2>();
+        data.new_row::<2>();
+        data.extend_last_row_from_slice(&[4, 5]);
+        let view = data.view::<1, 3>([1]);
+        assert!(view.len() == 2);
+        assert!(view[[1, 1, 1]] == 5);
+        assert!(view.get([1, 1, 1]) == Some(&5));
+        assert!(view.get([1, 2, 0]).is_none());
+        assert!(unsafe { *view.get_unchecked([1, 1, 0]) } == 4);
+        let inner = view.view::<1, 2>([1]);
+        assert!(inner[[1, 0]] == 4);
+        assert!(unsafe { inner.view_unchecked::<1, 1>([1]).as_slice() } == [4, 5]);
+        let owned = inner.clone().to_owned();
+        assert!(owned[[1, 1]] == 5);
+        assert!(owned.view::<1, 1>([0]).len() == 0);
+        let mut other = jagged_array::JaggedArray::<i32, Vec<u16>, 2>::new();
+        other.append_from_view(inner);
+        assert!(other.len() == 2);
+        assert!(other[[1, 1]] == 5);
+        data.view_mut::<1, 3>([1])[[1, 1, 0]] = 40;
+        assert!(data[[1, 1, 1, 0]] == 40);
+    }
+
+    #[test]
+    fn iter_indexed_test() {
+        let mut data = jagged_array::JaggedArray::<i32, Vec<u16>, 3>::new();
+        data.new_row::<0>();
+        data.new_row::<1>();
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[1, 2]);
+        data.new_row::<0>();
+        data.new_row::<0>();
+        data.new_row::<1>();
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[3]);
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[4]);
+        let indices = data.iter_indexed().map(|(index, _)| index).collect::<Vec<_>>();
+        assert!(indices == [[0, 1, 0], [0, 1, 1], [2, 1, 0], [2, 2, 0]]);
+        assert!(data.iter_indexed().all(|(index, &value)| data[index] == value));
+        assert!(data.iter_indexed().len() == 4);
+        let view = data.view::<1, 2>([2]);
+        let indices = view.iter_indexed().map(|(index, _)| index).collect::<Vec<_>>();
+        assert!(indices == [[1, 0], [2, 0]]);
+        assert!(view.iter().copied().collect::<Vec<_>>() == [3, 4]);
+        for (index, value) in data.iter_indexed_mut() {
+            *value = index.iter().sum::<usize>() as i32;
+        }
+        assert!(data.iter().copied().collect::<Vec<_>>() == [1, 2, 3, 4]);
+        data.view_mut::<1, 2>([2]).iter_mut().for_each(|x| *x = 0);
+        assert!(data.iter().copied().collect::<Vec<_>>() == [1, 2, 0, 0]);
+        let mut data = jagged_array::JaggedArray::<i32, Vec<u16>, 1>::new();
+        data.push_to_last_row(5);
+        data.push_to_last_row(6);
+        assert!(data.iter_indexed().collect::<Vec<_>>() == [([0], &5), ([1], &6)]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_rows_test() {
+        use rayon::prelude::*;
+        let mut data = jagged_array::JaggedArray::<usize, Vec<u32>, 3>::new();
+        for i in 0..1000 {
+            data.new_row::<0>();
+            for j in 0..i % 7 {
+                data.new_row::<1>();
+                data.extend_last_row((0..j).map(|k| i + k));
+            }
+        }
+        let expected = data
+            .rows::<2>()
+            .map(|row| row.iter().sum::<usize>())
+            .collect::<Vec<_>>();
+        let sums = data
+            .par_rows::<2>()
+            .map(|row| row.iter().sum::<usize>())
+            .collect::<Vec<_>>();
+        assert!(sums == expected);
+        assert!(data.par_iter().sum::<usize>() == expected.iter().sum::<usize>());
+        data.par_rows_mut::<2>().enumerate().for_each(|(i, mut row)| {
+            row.rows_mut::<1>().for_each(|mut row| {
+                for j in 0..row.len() {
+                    row[[j]] -= i;
+                }
+            })
+        });
+        assert!(data.iter_indexed().all(|([_, _, k], &value)| value == k));
+    }
+
+    #[test]
+    fn from_nested_test() {
+        let data = JaggedArray::<i32, Vec<u16>, 2>::from(vec![vec![1, 2], vec![], vec![3]]);
+        assert!(data.len() == 3);
+        assert!(data[[0, 1]] == 2);
+        assert!(data[[2, 0]] == 3);
+        let slices: &[&[i32]] = &[&[1, 2], &[], &[3]];
+        assert!(JaggedArray::<i32, Vec<u16>, 2>::from(slices) == data);
+        let data = JaggedArray::<i32, Vec<u16>, 3>::from(vec![vec![vec![1], vec![2, 3]], vec![]]);
+        assert!(data.len() == 2);
+        assert!(data[[0, 1, 1]] == 3);
+        assert!(data.view::<1, 2>([1]).len() == 0);
+        let collected = (0..3)
+            .map(|i| (0..i).map(move |j| 0..j))
+            .collect::<JaggedArray<usize, Vec<u8>, 3>>();
+        assert!(collected.view::<1, 2>([2]).len() == 2);
+        assert!(collected[[2, 1, 0]] == 0);
+        let collected = [[[[1, 2]], [[3, 4]]]]
+            .into_iter()
+            .collect::<JaggedArray<i32, Vec<u8>, 4>>();
+        assert!(collected[[0, 1, 0, 1]] == 4);
+    }
+
+    #[test]
+    fn to_nested_vec_test() {
+        let nested = vec![vec![vec![1, 2], vec![]], vec![], vec![vec![3], vec![4, 5]]];
+        let data = JaggedArray::<i32, Vec<u16>, 3>::from(nested.clone());
+        assert!(data.to_nested_vec() == nested);
+        assert!(data.view::<1, 2>([2]).to_nested_vec() == nested[2]);
+        assert!(data.view::<0, 3>([]).to_owned().to_nested_vec() == nested);
+        assert!(data.view::<1, 2>([2]).to_owned().into_nested_vec() == nested[2]);
+        assert!(data.view::<0, 3>([]).to_owned().into_nested_vec() == nested);
+        assert!(data.into_nested_vec() == nested);
+    }
+
+    #[test]
+    fn jagged_macro_test() {
+        let data = jagged![1, 2, 3];
+        assert!(data.as_slice() == [1, 2, 3]);
+        let data = jagged![[1, 2], [3], []];
+        assert!(data.to_nested_vec() == vec![vec![1, 2], vec![3], vec![]]);
+        let data = jagged![u16; [[1], [2, 3]], [], [[4]]];
+        assert!(data.to_nested_vec() == vec![vec![vec![1], vec![2, 3]], vec![], vec![vec![4]]]);
+        let data = jagged![u8; [[[1], []], [[2, 3]]], [[[4]]]];
+        assert!(data[[0, 1, 0, 1]] == 3);
+        assert!(data[[1, 0, 0, 0]] == 4);
+        let data: JaggedArray<i32, Vec<usize>, 2> = jagged![[], []];
+        assert!(data.len() == 2);
+        let x = 2;
+        assert!(jagged![-1, x + 1].as_slice() == [-1, 3]);
+        assert!(jagged![i32; x + 1].as_slice() == [3]);
+        assert!(jagged![u8; [-1, 2], [-x * 2]].to_nested_vec() == vec![vec![-1, 2], vec![-4]]);
+        let data: JaggedArray<i32, Vec<usize>, 1> = jagged![];
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn insert_row_test() {
+        let mut data = jagged![u16; [1, 2], [3]];
+        data.insert_row::<1, 1>([0], jagged![u16; 4]);
+        data.insert_row::<1, 1>([3], jagged![u16; 5, 6]);
+        data.insert_row::<1, 1>([2], JaggedArray::new());
+        assert!(data == jagged![u16; [4], [1, 2], [], [3], [5, 6]]);
+        let mut data = jagged![u16; [[1], [2, 3]], [], [[4]]];
+        data.insert_row::<2, 1>([1, 0], jagged![u16; 5]);
+        data.insert_row::<2, 1>([0, 2], jagged![u16; 6, 7]);
+        data.insert_row::<1, 2>([3], jagged![u16; [8], [9]]);
+        data.insert_row::<1, 2>([0], jagged![u16; [], [10]]);
+        assert!(data == jagged![u16; [[], [10]], [[1], [2, 3], [6, 7]], [[5]], [[4]], [[8], [9]]]);
+        let mut data = jagged![u8; [[[1], []], [[2, 3]]], [[[4]]]];
+        data.insert_row::<3, 1>([0, 1, 0], jagged![u8; 5]);
+        data.insert_row::<2, 2>([1, 0], jagged![u8; [6]]);
+        data.insert_row::<1, 3>([1], jagged![u8; [[7], [8]]]);
+        assert!(data == jagged![u8; [[[1], []], [[5], [2, 3]]], [[[7], [8]]], [[[6]], [[4]]]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_row_out_of_bounds_test() {
+        let mut data = jagged![[1, 2], [3]];
+        data.insert_row::<1, 1>([3], jagged![4]);
+    }
+
+    #[test]
+    fn remove_rows_test() {
+        let mut data = jagged![u16; [[1], [2, 3]], [[4]], [[], [5]]];
+        data.remove_rows(2..3);
+        assert!(data == jagged![u16; [[1], [2, 3]], [[4]]]);
+        data.remove_rows_at::<1>([0], 1..2);
+        assert!(data == jagged![u16; [[1]], [[4]]]);
+        data.remove_rows_at::<2>([1, 0], 0..1);
+        data.remove_rows_at::<1>([0], 0..0);
+        assert!(data.to_nested_vec() == vec![vec![vec![1]], vec![vec![]]]);
+        data.remove_rows_at::<1>([1], 0..1);
+        assert!(data == jagged![u16; [[1]], []]);
+        let mut data = jagged![u8; [[[1], []], [[2, 3]]], [[[4]], [[5], [6, 7]]]];
+        data.remove_rows_at::<2>([1, 1], 0..1);
+        data.remove_rows_at::<1>([0], 0..1);
+        assert!(data == jagged![u8; [[[2, 3]]], [[[4]], [[6, 7]]]]);
+        data.remove_rows(0..1);
+        assert!(data == jagged![u8; [[[4]], [[6, 7]]]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_rows_out_of_bounds_test() {
+        let mut data = jagged![[1, 2], [3]];
+        data.remove_rows_at::<1>([1], 0..2);
+    }
+
+    #[test]
+    fn drain_rows_test() {
+        let mut data = jagged![u16; [[1], []], [], [[2, 3]], [[]]];
+        let drained = data.drain_rows(1..3);
+        assert!(drained.to_nested_vec() == vec![vec![], vec![vec![2, 3]]]);
+        assert!(data.to_nested_vec() == vec![vec![vec![1], vec![]], vec![vec![]]]);
+        assert!(data.drain_rows(0..0).is_empty());
+        assert!(data.drain_rows(1..2).to_nested_vec() == vec![vec![Vec::<i32>::new()]]);
+        assert!(data.validate() == Ok(()) && data.buffer_len() == 1);
+        let mut data = jagged![u16; [String::from("a")], [String::from("b")]];
+        let drained = data.drain_rows(0..1);
+        assert!(drained.into_nested_vec() == vec![vec![String::from("a")]]);
+        assert!(data.len() == 1);
+    }
+
+    #[test]
+    fn pop_row_test() {
+        let mut data = jagged![u16; [[1], [2, 3]], [[4]], []];
+        assert!(data.pop_row::<1, 1>().is_none());
+        assert!(data.pop_row::<0, 2>() == Some(JaggedArray::new()));
+        assert!(data.pop_row::<1, 1>() == Some(jagged![u16; 4]));
+        assert!(data == jagged![u16; [[1], [2, 3]], []]);
+        assert!(data.pop_row::<0, 2>() == Some(JaggedArray::new()));
+        assert!(data.pop_row::<0, 2>() == Some(jagged![u16; [1], [2, 3]]));
+        assert!(data.pop_row::<0, 2>().is_none());
+        assert!(data.is_empty() && data.len() == 0);
+        let mut data = jagged![u8; [[[1], []], [[2, 3]]], [[[4]], [[5], [6, 7]]]];
+        assert!(data.pop_row::<2, 1>() == Some(jagged![u8; 6, 7]));
+        assert!(data.pop_row::<1, 2>() == Some(jagged![u8; [5]]));
+        assert!(data == jagged![u8; [[[1], []], [[2, 3]]], [[[4]]]]);
+    }
+
+    #[test]
+    fn split_test() {
+        let mut data = jagged![u16; [[1]], [[], [2, 3]], [], [[4]]];
+        let mut tail = data.split_off(1);
+        assert!(data == jagged![u16; [[1]]]);
+        let last = tail.split_off(1);
+        assert!(tail == jagged![u16; [[], [2, 3]]]);
+        assert!(last.to_nested_vec() == vec![vec![], vec![vec![4]]]);
+        assert!(data.split_off(1).is_empty());
+        assert!(data.split_off(0).len() == 1 && data.len() == 0);
+        let mut data = jagged![1, 2, 3];
+        assert!(data.split_off(1).as_slice() == [2, 3]);
+        let data = jagged![u8; [[[1], []], [[2, 3]]], [[[4]], [[5], [6, 7]], []]];
+        let view = data.view::<1, 3>([1]);
+        let (left, right) = view.split_at(1);
+        assert!(left.to_owned().into_nested_vec() == vec![vec![vec![4]]]);
+        assert!(right.len() == 2);
+        assert!(right[[0, 1, 1]] == 7);
+        let (left, right) = right.split_at(2);
+        assert!(left.to_nested_vec() == vec![vec![vec![5], vec![6, 7]], vec![]]);
+        assert!(right.is_empty() && right.len() == 0);
+        let mut data = jagged![u16; [1, 2], [3], [4, 5]];
+        let mut view = data.view_mut::<0, 2>([]);
+        let (mut left, mut right) = view.split_at_mut(1);
+        left[[0, 1]] = 20;
+        right.rows_mut::<1>().for_each(|mut row| row[[0]] *= 10);
+        assert!(data == jagged![u16; [1, 20], [30], [40, 5]]);
+    }
+
+    #[test]
+    fn index_overflow_test() {
+        let mut data = JaggedArray::<u8, Vec<u8>, 3>::new();
+        data.new_row::<0>();
+        data.new_row::<1>();
+        data.extend_last_row_from_slice(&[0; 255]);
+        assert!(data.try_push_to_last_row(0) == Err(JaggedArrayError::IndexOverflow));
+        assert!(data.try_new_row::<1>().is_ok());
+        assert!(data.buffer_len() == 255);
+        let mut other = JaggedArray::<u8, Vec<u8>, 2>::new();
+        other.new_row::<0>();
+        other.push_to_last_row(0);
+        assert!(data.try_append(other.clone()) == Err(JaggedArrayError::IndexOverflow));
+        data.remove_rows_at::<2>([0, 0], 0..1);
+        assert!(data.try_append(other).is_ok());
+        assert!(data.buffer_len() == 255);
+        assert!(data.view::<1, 2>([0]).to_nested_vec()[1..] == [vec![], vec![0]]);
+        let result = std::panic::catch_unwind(|| {
+            let mut data = JaggedArray::<u8, Vec<u8>, 2>::new();
+            data.new_row::<0>();
+            data.extend_last_row(std::iter::repeat_n(0, 256));
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn try_mutation_test() {
+        let mut data = jagged![u16; [[1], [2, 3]], [[4]]];
+        let expected = data.clone();
+        assert!(data.try_remove_rows(1..3) == Err(JaggedArrayError::OutOfBounds));
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 2..1;
+        assert!(data.try_remove_rows(reversed) == Err(JaggedArrayError::OutOfBounds));
+        assert!(data.try_remove_rows_at::<2>([0, 2], 0..0) == Err(JaggedArrayError::OutOfBounds));
+        assert!(data.try_drain_rows(0..3).is_err());
+        assert!(data.try_split_off(3).is_err());
+        assert!(data.try_insert_row::<2, 1>([2, 0], jagged![u16; 5]).is_err());
+        assert!(data.try_insert_row::<2, 1>([1, 2], jagged![u16; 5]).is_err());
+        assert!(data == expected);
+        assert!(data.try_drain_rows(1..2).unwrap() == jagged![u16; [[4]]]);
+        assert!(data.try_insert_row::<2, 1>([0, 2], jagged![u16; 5]).is_ok());
+        assert!(data == jagged![u16; [[1], [2, 3], [5]]]);
+
+        let mut data = JaggedArray::<i32, tinyvec::ArrayVec<[u16; 2]>, 2>::new();
+        data.new_row::<0>();
+        assert!(data.try_new_row::<0>() == Err(JaggedArrayError::CapacityExhausted));
+        assert!(data.try_push_to_last_row(1).is_ok());
+        assert!(data.len() == 1);
+
+        let mut empty = JaggedArray::<i32, Vec<u16>, 3>::new();
+        assert!(!empty.remove_last_row::<0>());
+        assert!(empty.try_remove_last_row::<0>() == Err(JaggedArrayError::OutOfBounds));
+        assert!(empty.try_remove_last_row::<1>() == Err(JaggedArrayError::OutOfBounds));
+        assert!(empty.try_truncate::<0>(1) == Err(JaggedArrayError::OutOfBounds));
+        assert!(empty.try_truncate::<0>(0).is_ok());
+        assert!(empty == JaggedArray::new());
+        let mut narrow = JaggedArray::<u8, Vec<u8>, 2>::new();
+        narrow.new_row::<0>();
+        assert!(narrow.try_extend_last_row(0..=255) == Err(JaggedArrayError::IndexOverflow));
+        assert!(narrow.buffer_len() == 0);
+
+        let mut data = jagged![u16; [[1]], [[2], [3]]];
+        assert!(data.try_remove_last_row::<1>().is_ok());
+        assert!(data.validate() == Ok(()));
+        assert!(data == jagged![u16; [[1]], [[2]]]);
+        assert!(data.try_truncate::<1>(1).is_ok());
+        assert!(data.validate() == Ok(()));
+        assert!(data.to_nested_vec() == vec![vec![vec![1]], vec![]]);
+        assert!(data.try_truncate::<1>(0).is_ok());
+        assert!(data.validate() == Ok(()));
+        assert!(data.to_nested_vec() == vec![Vec::<Vec<i32>>::new()]);
+
+        let error = Vec::<u8>::new().try_reserve(usize::MAX).unwrap_err();
+        let error = JaggedArrayError::AllocationFailure(error);
+        assert!(std::error::Error::source(&error).is_some());
+        assert!(JaggedArrayError::OutOfBounds.to_string() == "index out of bounds");
+    }
+
+    #[test]
+    fn try_reserve_test() {
+        let mut data = JaggedArray::<i32, Vec<u16>, 3>::try_with_capacity([2, 4, 8]).unwrap();
+        assert!(data.buffer_capacity() >= 8);
+        assert!(data.try_reserve([0, 0, usize::MAX]).is_err());
+        assert!(data.try_reserve([1, 1, 1]).is_ok());
+        type Small = tinyvec::ArrayVec<[u16; 3]>;
+        assert!(JaggedArray::<i32, Small, 2>::try_with_capacity([3, 0]).is_err());
+        let mut data = JaggedArray::<i32, Small, 2>::try_with_capacity([2, 0]).unwrap();
+        data.new_row::<0>();
+        let other = JaggedArray::<i32, Small, 2>::from(vec![vec![1], vec![2]]);
+        assert!(data.try_append(other.clone()) == Err(JaggedArrayError::CapacityExhausted));
+        assert!(data.try_insert_row::<1, 1>([0], JaggedArray::new()).is_ok());
+        assert!(data.try_insert_row::<1, 1>([0], JaggedArray::new()).is_err());
+        assert!(data.len() == 2);
+    }
+
+    #[test]
+    fn adaptive_test() {
+        let mut data = AdaptiveJaggedArray::<u32, 3>::new();
+        data.new_row::<0>();
+        for i in 0..70000 {
+            data.new_row::<1>();
+            data.push_to_last_row(i);
+        }
+        assert!(data.index_size() == 4);
+        assert!(data.len() == 1 && data.buffer_len() == 70000);
+        assert!((0..70000).all(|i| data[[0, i as usize, 0]] == i));
+        assert!(data.pop_from_last_row() == Some(69999));
+        let mut data = AdaptiveJaggedArray::from(jagged![u16; [1, 2], [3]]);
+        data.extend_last_row(0..u16::MAX as u32 - 3);
+        assert!(data.index_size() == 2);
+        data.extend_last_row_from_slice(&[4]);
+        assert!(data.index_size() == 4);
+        assert!(data.get([1, 65533]) == Some(&4));
+        assert!(data.get([2, 0]).is_none());
+        data.clear();
+        assert!(data.is_empty() && data.index_size() == 2);
+
+        let narrow = AdaptiveJaggedArray::from(jagged![u16; [1, 2], [3]]);
+        let wide = AdaptiveJaggedArray::U32(jagged![u32; [1, 2], [3]]);
+        assert!(narrow == wide && narrow != AdaptiveJaggedArray::U64(jagged![u64; [1, 2]]));
+        let hash = |data: &AdaptiveJaggedArray<i32, 2>| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            std::hash::Hash::hash(data, &mut hasher);
+            std::hash::Hasher::finish(&hasher)
+        };
+        assert!(hash(&narrow) == hash(&wide));
+        let mut data = AdaptiveJaggedArray::<u32, 2>::new();
+        data.new_row::<0>();
+        data.extend_last_row((0..70000).filter(|i| i % 2 == 0));
+        assert!(data.index_size() == 2 && data.buffer_len() == 35000);
+        data.extend_last_row((0..100_000).filter(|_| false));
+        assert!(data.index_size() == 2 && data.buffer_len() == 35000);
+        struct Underreporting(std::ops::Range<u32>);
+        impl Iterator for Underreporting {
+            type Item = u32;
+            fn next(&mut self) -> Option<u32> {
+                self.0.next()
+            }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (0, Some(1))
+            }
+        }
+        assert!(data.try_extend_last_row(Underreporting(0..35000)).is_ok());
+        assert!(data.index_size() == 4 && data.get([0, 69999]) == Some(&34999));
+        let mut data = AdaptiveJaggedArray::<u32, 2>::new();
+        data.new_row::<0>();
+        data.extend_last_row(std::iter::successors(Some(0), |&i| (i < 69999).then_some(i + 1)));
+        assert!(data.index_size() == 4 && data.get([0, 69999]) == Some(&69999));
+    }
+
+    #[test]
+    fn append_test() {
+        let mut data = jagged![u16; [[1]], [[2]]];
+        data.append(jagged![u16; [3], [4, 5]]);
+        data.append(jagged![u16; [[6]], []]);
+        data.append_from_view(jagged![u16; [[7], [8]]].view::<1, 2>([0]));
+        assert!(data == jagged![u16; [[1]], [[2], [3], [4, 5]], [[6]], [[7], [8]]]);
+    }
+
+    #[test]
+    fn validate_test() {
+        let mut data = jagged![[[1, 2], [3]], [[4]]];
+        assert!(data.validate().is_ok());
+        data.indices[1][2] = 1;
+        assert!(
+            data.validate()
+                == Err(JaggedArrayError::DecreasingOffsets {
+                    level: 1,
+                    position: 2
+                })
+        );
+        data.indices[1][2] = 3;
+        data.buffer.pop();
+        assert!(
+            data.validate()
+                == Err(JaggedArrayError::LengthMismatch {
+                    level: 1,
+                    expected: 3,
+                    actual: 4
+                })
+        );
+        data.indices[0].clear();
+        assert!(data.validate() == Err(JaggedArrayError::MissingOffsets { level: 0 }));
+        let data = JaggedArray::<u8, Vec<u16>, 2> {
+            indices: [vec![1, 2]].into(),
+            buffer: vec![0, 0],
+        };
+        assert!(data.validate() == Err(JaggedArrayError::NonZeroStart { level: 0, offset: 1 }));
+        let data = jagged![[1], [2, 3], [4]];
+        assert!(data.view::<0, 2>([]).split_at(1).1.to_owned().validate().is_ok());
+    }
+
+    #[test]
+    fn raw_parts_test() {
+        let data = jagged![u8; [[1], [2, 3]], [[4]]];
+        let (indices, buffer) = data.clone().into_raw_parts();
+        let view = JaggedArrayView::from_raw_parts(
+            GenericArray::from([&indices[0][1..], &indices[1][2..]]),
+            &buffer[3..],
+        )
+        .unwrap();
+        assert!(view.to_owned() == data.view::<0, 3>([]).split_at(1).1.to_owned());
+        assert!(JaggedArray::from_raw_parts(indices, buffer).unwrap() == data);
+    }
+
+    #[test]
+    fn offsets_test() {
+        let data = jagged![u16; [[1, 2], [3]], [[4], [], [5, 6, 7]]];
+        let (_, right) = data.split_at(1);
+        assert!(right.offsets::<0>() == [2, 5]);
+        assert!(right.offsets::<1>() == [3, 4, 4, 7]);
+        assert!(right.row_lengths::<1>().rev().collect::<Vec<_>>() == [3, 0, 1]);
+        assert!(right.to_owned().offsets::<1>() == [0, 1, 1, 4]);
+        assert!(data.row_lengths::<1>().len() == data.len_at::<1>());
+        assert!(data.len_at::<2>() == data.buffer_len());
+        let mut data = JaggedArray::<u8, Vec<u16>, 1>::new();
+        data.extend_last_row_from_slice(&[1, 2, 3]);
+        assert!(data.len_at::<0>() == 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let data = jagged![u16; [[1, 2], [3]], [], [[], [4]]];
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(json == "[[[1,2],[3]],[],[[],[4]]]");
+        assert!(serde_json::from_str::<JaggedArray<usize, Vec<u16>, 3>>(&json).unwrap() == data);
+        let (_, right) = data.split_at(1);
+        assert!(serde_json::to_string(&right).unwrap() == "[[],[[],[4]]]");
+        let bytes = bincode::serialize(&right).unwrap();
+        let owned = bincode::deserialize::<jagged_array::JaggedArrayOwnedView<usize, u16, 3>>(&bytes);
+        assert!(owned.unwrap() == right.to_owned());
+        let bytes = bincode::serialize(&data).unwrap();
+        assert!(bincode::deserialize::<JaggedArray<usize, Vec<u16>, 3>>(&bytes).unwrap() == data);
+        assert!(bincode::deserialize::<JaggedArray<usize, Vec<u8>, 3>>(&bytes).is_err());
+        let broken = (vec![vec![0u16, 2], vec![0, 1, 3]], vec![1usize, 2]);
+        let bytes = bincode::serialize(&broken).unwrap();
+        let error = bincode::deserialize::<JaggedArray<usize, Vec<u16>, 3>>(&bytes).unwrap_err();
+        assert!(error.to_string() == "offsets of level 1 end at 3, but 2 entries follow");
+        let json = format!("{:?}", vec![0; 255]);
+        assert!(serde_json::from_str::<JaggedArray<u8, Vec<u8>, 2>>(&format!("[{json}]")).is_ok());
+        assert!(serde_json::from_str::<JaggedArray<u8, Vec<u8>, 2>>(&format!("[{json},[1]]")).is_err());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn binary_test() {
+        let mut data = JaggedArray::<f32, Vec<u64>, 3>::new();
+        for i in 0..100 {
+            data.new_row::<0>();
+            for j in 0..i % 5 {
+                data.new_row::<1>();
+                data.extend_last_row((0..j).map(|k| (i * k) as f32));
+            }
+        }
+        let path = std::env::temp_dir().join(format!("jaggedarray-{}.bin", std::process::id()));
+        data.write_to(std::fs::File::create(&path).unwrap()).unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let bytes = unsafe { memmap2::Mmap::map(&file).unwrap() };
+        let view = JaggedArrayView::<f32, u64, 3>::from_bytes(&bytes).unwrap();
+        assert!(view.to_owned() == data.view::<0, 3>([]).to_owned());
+        assert!(
+            JaggedArrayView::<f32, u32, 3>::from_bytes(&bytes)
+                == Err(JaggedArrayError::InvalidFormat("index width mismatch"))
+        );
+        assert!(
+            JaggedArrayView::<f32, u64, 2>::from_bytes(&bytes)
+                == Err(JaggedArrayError::InvalidFormat("dimension mismatch"))
+        );
+        assert!(
+            JaggedArrayView::<u32, u64, 3>::from_bytes(&bytes)
+                == Err(JaggedArrayError::InvalidFormat("value type mismatch"))
+        );
+        assert!(
+            JaggedArrayView::<f32, u64, 3>::from_bytes(&bytes[..bytes.len() - 16])
+                == Err(JaggedArrayError::InvalidFormat("file is truncated"))
+        );
+        drop(bytes);
+        std::fs::remove_file(path).unwrap();
+
+        let mut bytes = Vec::new();
+        jagged![i32; [1u16, 2], [3]].write_to(&mut bytes).unwrap();
+        let mut storage = vec![0u128; bytes.len() / 16];
+        bytemuck::cast_slice_mut(&mut storage).copy_from_slice(&bytes);
+        let bytes = bytemuck::cast_slice::<_, u8>(&storage);
+        assert!(JaggedArrayView::<u16, i32, 2>::from_bytes(bytes).is_ok());
+        assert!(
+            JaggedArrayView::<u16, u32, 2>::from_bytes(bytes)
+                == Err(JaggedArrayError::InvalidFormat("index type mismatch"))
+        );
+        assert!(
+            JaggedArrayView::<u16, i32, 2>::from_bytes(&bytes[..bytes.len() - 10])
+                == Err(JaggedArrayError::InvalidFormat("file is truncated"))
+        );
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn arrow_test() {
+        use arrow_array::cast::AsArray;
+        use arrow_array::types::{Float64Type, Int32Type, UInt8Type};
+        use arrow_array::{LargeListArray, ListArray};
+        let data = jagged![i32; [[1u8, 2], [3]], [], [[], [4]]];
+        let copy = data.clone();
+        let pointer = copy.buffer.as_ptr();
+        let list = copy.into_arrow::<UInt8Type>();
+        assert!(list.value_offsets() == [0, 2, 2, 4]);
+        assert!(list.values().as_list::<i32>().value_offsets() == [0, 2, 3, 3, 4]);
+        let round_trip = JaggedArray::<u8, Vec<i32>, 3>::try_from_arrow::<UInt8Type>(list).unwrap();
+        assert!(round_trip == data && round_trip.buffer.as_ptr() == pointer);
+        let (_, right) = data.split_at(2);
+        let list = right.to_arrow::<UInt8Type>();
+        assert!(list.value_offsets() == [0, 2]);
+        let sliced = data.clone().into_arrow::<UInt8Type>().slice(2, 1);
+        let sliced = JaggedArray::<u8, Vec<i32>, 3>::try_from_arrow::<UInt8Type>(sliced).unwrap();
+        assert!(sliced.view::<0, 3>([]).to_owned() == right.to_owned());
+        let mut large = JaggedArray::<f64, Vec<i64>, 2>::new();
+        large.new_row::<0>();
+        large.extend_last_row_from_slice(&[0.5, 1.5]);
+        let list: LargeListArray = large.clone().into_arrow::<Float64Type>();
+        let list = JaggedArray::<f64, Vec<i64>, 2>::try_from_arrow::<Float64Type>(list).unwrap();
+        assert!(list == large);
+        let list = ListArray::from_iter_primitive::<Int32Type, _, _>([Some(vec![Some(1)]), None]);
+        assert!(
+            JaggedArray::<i32, Vec<i32>, 2>::try_from_arrow::<Int32Type>(list.clone())
+                == Err(JaggedArrayError::NullValues)
+        );
+        assert!(
+            JaggedArray::<i32, Vec<i32>, 3>::try_from_arrow::<Int32Type>(list.slice(0, 1))
+                == Err(JaggedArrayError::InvalidFormat("unexpected data type"))
+        );
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn ndarray_test() {
+        let data = jagged![u16; [[1, 2], [3]], [], [[4], [], [5, 6, 7]]];
+        let (array, mask) = data.to_padded_array_with_mask(0);
+        assert!(array.shape() == [3, 3, 3]);
+        assert!(array[[2, 2, 2]] == 7 && array[[0, 1, 1]] == 0);
+        assert!(mask.iter().filter(|&&present| present).count() == data.buffer_len());
+        let lengths = mask.map_axis(ndarray::Axis(2), |row| row.iter().filter(|&&x| x).count());
+        let dense = JaggedArray::<i32, Vec<u16>, 3>::from_array(array.view(), lengths.view());
+        let expected = jagged![u16; [[1, 2], [3], []], [[], [], []], [[4], [], [5, 6, 7]]];
+        assert!(dense.unwrap() == expected);
+        let (_, right) = data.split_at(2);
+        assert!(right.to_padded_array(-1) == ndarray::array![[[4, -1, -1], [-1, -1, -1], [5, 6, 7]]]);
+        let too_long = ndarray::Array::from_elem([3, 3], 4);
+        assert!(
+            JaggedArray::<i32, Vec<u16>, 3>::from_array(array.view(), too_long.view())
+                == Err(JaggedArrayError::OutOfBounds)
+        );
+        let mismatched = ndarray::Array::from_elem([3, 2], 1);
+        assert!(
+            JaggedArray::<i32, Vec<u16>, 3>::from_array(array.view(), mismatched.view())
+                == Err(JaggedArrayError::InvalidFormat("lengths shape mismatch"))
+        );
+        let wide = ndarray::Array::from_elem([1, 300], 0u8);
+        let lengths = ndarray::array![300];
+        assert!(
+            JaggedArray::<u8, Vec<u8>, 2>::from_array(wide.view(), lengths.view())
+                == Err(JaggedArrayError::IndexOverflow)
+        );
+    }
+
+    #[test]
+    fn csr_test() {
+        use crate::csr::CsrView;
+        let data = jagged![u16; [(1u8, 2), (0, 1)], [], [(2, 3), (1, 4)], [(0, 5)]];
+        let (_, right) = data.split_at(2);
+        let matrix = CsrView::new(right, 3).unwrap();
+        assert!(matrix.shape() == (2, 3) && matrix.nnz() == 3);
+        assert!(matrix.mul_vec(&[1, 10, 100]) == [340, 5]);
+        assert!(matrix.to_csc() == jagged![u16; [(1u8, 5)], [(0, 4)], [(0, 3)]]);
+        let full = CsrView::new(data.view::<0, 2>([]), 3).unwrap();
+        let transposed = full.to_csc();
+        let transposed = CsrView::new(transposed.view::<0, 2>([]), 4).unwrap();
+        assert!(transposed.to_csc() == jagged![u16; [(0u8, 1), (1, 2)], [], [(1, 4), (2, 3)], [(0, 5)]]);
+        assert!(CsrView::new(data.view::<0, 2>([]), 2) == Err(JaggedArrayError::OutOfBounds));
+        let tall = JaggedArray::<(u8, i32), Vec<u16>, 2>::from(vec![vec![(0, 1)]; 300]);
+        let tall = CsrView::new(tall.view::<0, 2>([]), 1).unwrap();
+        assert!(tall.try_to_csc() == Err(JaggedArrayError::IndexOverflow));
+        #[cfg(feature = "sprs")]
+        {
+            let data = jagged![u32; [(1u32, 2), (0, 1)], [], [(2, 3), (1, 4)], [(0, 5)]];
+            let matrix = CsrView::new(data.view::<0, 2>([]), 3).unwrap().to_sprs().unwrap();
+            assert!(matrix.get(2, 1) == Some(&4) && matrix.nnz() == 5);
+            let sorted = jagged![u32; [(0u32, 1), (1, 2)], [], [(1, 4), (2, 3)], [(0, 5)]];
+            assert!(JaggedArray::from(matrix.clone()) == sorted);
+            assert!(JaggedArray::from(matrix.to_csc()) == sorted);
+            let duplicate = jagged![u32; [(0u32, 1), (0, 2)]];
+            let duplicate = CsrView::new(duplicate.view::<0, 2>([]), 1).unwrap();
+            assert!(matches!(duplicate.to_sprs(), Err(JaggedArrayError::InvalidFormat(_))));
+        }
+    }
+
+    #[test]
+    fn dynamic_test() {
+        let data = jagged![u16; [[1, 2], [3]], [], [[4], [], [5, 6]]];
+        let dynamic = JaggedArrayDyn::from(data.clone());
+        assert!(dynamic.dim() == 3 && dynamic.len() == 3);
+        assert!(dynamic.get(&[2, 2, 1]) == Some(&6));
+        assert!(dynamic.get(&[2, 1, 0]).is_none() && dynamic.get(&[3, 0, 0]).is_none());
+        assert!(dynamic.get(&[0, 0]).is_none());
+        let row = dynamic.view(&[2]);
+        assert!(row.dim() == 2 && row.len() == 3 && row.as_slice() == [4, 5, 6]);
+        assert!(row.view(&[2]).as_slice() == [5, 6] && row.get(&[2, 0]) == Some(&5));
+        assert!(row.to_owned() == JaggedArrayDyn::from(data.view::<1, 2>([2]).convert_index()));
+        assert!(dynamic.try_view(&[1, 0]) == Err(JaggedArrayError::OutOfBounds));
+        assert!(dynamic.try_view(&[0, 0, 0]) == Err(JaggedArrayError::OutOfBounds));
+        let view = JaggedArrayView::<_, _, 2>::try_from(row).unwrap();
+        assert!(view == data.view::<1, 2>([2]));
+        let view = dynamic::JaggedArrayDynView::from(data.view::<1, 2>([0]));
+        assert!(view.view(&[1]).as_slice() == [3]);
+        assert!(JaggedArray::<_, _, 3>::try_from(dynamic.clone()).unwrap() == data);
+        assert!(
+            JaggedArray::<_, _, 2>::try_from(dynamic)
+                == Err(JaggedArrayError::DimensionMismatch { expected: 2, actual: 3 })
+        );
+        let mut flat = JaggedArrayDyn::<i32, Vec<u8>>::new(1);
+        flat.extend_last_row_from_slice(&[1, 2]);
+        assert!(flat.pop_from_last_row() == Some(2) && flat.get(&[0]) == Some(&1));
+        assert!(flat.try_new_row(0) == Err(JaggedArrayError::OutOfBounds));
+        let mut narrow = JaggedArrayDyn::<i32, Vec<u8>>::new(2);
+        narrow.new_row(0);
+        assert!(narrow.try_extend_last_row_from_slice(&[0; 256]) == Err(JaggedArrayError::IndexOverflow));
+        assert!(narrow.validate().is_ok() && narrow.buffer_len() == 0);
+    }
+
+    #[test]
+    fn convert_index_test() {
+        let data = jagged![u16; [[1], [2, 3]], [[4, 5, 6]]];
+        let wide = data.clone().convert_index::<Vec<u64>>();
+        assert!(wide.to_nested_vec() == data.to_nested_vec());
+        let narrow = wide.try_convert_index::<Vec<u8>>().unwrap();
+        assert!(narrow.to_nested_vec() == data.to_nested_vec());
+        let row = data.view::<1, 2>([1]).convert_index::<Vec<u32>>();
+        assert!(row == jagged![u32; [4, 5, 6]]);
+        let mut long = JaggedArray::<u8, Vec<u32>, 2>::from(vec![vec![0; 300]]);
+        long.new_row::<0>();
+        long.push_to_last_row(1);
+        let long = long.try_convert_index::<Vec<u8>>().unwrap_err().1;
+        assert!(long.view::<1, 1>([1]).try_convert_index::<Vec<u8>>().unwrap().as_slice() == [1]);
+        assert!(long.view::<0, 2>([]).try_convert_index::<Vec<u8>>() == Err(JaggedArrayError::IndexOverflow));
+        let deep = JaggedArray::<u8, Vec<u32>, 3>::from(vec![vec![vec![0; 300]]]);
+        assert!(deep.view::<0, 3>([]).try_convert_index::<Vec<u8>>() == Err(JaggedArrayError::IndexOverflow));
+        let (error, deep) = deep.try_convert_index::<Vec<u8>>().unwrap_err();
+        assert!(error == JaggedArrayError::IndexOverflow && deep.buffer_len() == 300);
+        let default = jagged![[1, 2], [3]];
+        assert!(default.view::<0, 2>([]).convert_index::<Vec<u64>>() == jagged![u64; [1, 2], [3]]);
+        assert!(default.convert_index::<Vec<u64>>() == jagged![u64; [1, 2], [3]]);
+        assert!(std::panic::catch_unwind(|| data.convert_index::<Vec<u8>>()).is_err());
+    }
+
     #[test]
     fn push_4d_test() {
         let mut data = jagged_array::JaggedArray::<i32, Vec<u16>, 4>::new();
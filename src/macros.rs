@@ -0,0 +1,56 @@
+/// Creates a [`JaggedArray`](crate::JaggedArray) from nested array literals.
+///
+/// The dimension of the array is inferred from the nesting depth of the first row.
+/// The index type defaults to `usize` and can be specified explicitly before a semicolon.
+///
+/// # Example
+///
+/// ```
+/// use jaggedarray::jagged;
+/// let data = jagged![[1, 2], [3], []];
+/// assert!(data[[0, 1]] == 2);
+/// assert!(data[[1, 0]] == 3);
+/// let data = jagged![u16; [[1], [2, 3]], [[4]]];
+/// assert!(data[[0, 1, 1]] == 3);
+/// assert!(data[[1, 0, 0]] == 4);
+/// ```
+#[macro_export]
+macro_rules! jagged {
+    (@dim [$($first:tt)*] $($rest:tt)*) => {
+        1 + $crate::jagged!(@dim $($first)*)
+    };
+    (@dim $($rest:tt)*) => {
+        1
+    };
+    (@row $array:ident, $dim:expr; [$([$($row:tt)*]),* $(,)?]) => {
+        $array.new_row::<{ $dim }>();
+        $($crate::jagged!(@row $array, $dim + 1; [$($row)*]);)*
+    };
+    (@row $array:ident, $dim:expr; [$($value:expr),* $(,)?]) => {
+        $array.new_row::<{ $dim }>();
+        $array.extend_last_row_from_slice(&[$($value),*]);
+    };
+    (@fill $array:ident; $([$($row:tt)*]),* $(,)?) => {
+        $($crate::jagged!(@row $array, 0; [$($row)*]);)*
+    };
+    ($([$($row:tt)*]),+ $(,)?) => {
+        $crate::jagged![usize; $([$($row)*]),+]
+    };
+    ($($value:expr),* $(,)?) => {
+        $crate::jagged![usize; $($value),*]
+    };
+    ($index:ty; $([$($row:tt)*]),+ $(,)?) => {{
+        let mut array = $crate::JaggedArray::<
+            _,
+            ::std::vec::Vec<$index>,
+            { $crate::jagged!(@dim $([$($row)*])*) },
+        >::new();
+        $crate::jagged!(@fill array; $([$($row)*]),+);
+        array
+    }};
+    ($index:ty; $($value:expr),* $(,)?) => {{
+        let mut array = $crate::JaggedArray::<_, ::std::vec::Vec<$index>, 1>::new();
+        array.extend_last_row_from_slice(&[$($value),*]);
+        array
+    }};
+}
@@ -0,0 +1,76 @@
+use generic_array::ArrayLength;
+use ndarray::{ArrayView, Dim, Dimension};
+use num::traits::{AsPrimitive, ConstZero};
+use num::Num;
+use typenum::{Const, NonZero, ToUInt, B1, U};
+
+use crate::error::JaggedArrayError;
+use crate::jagged_array::{to_index, JaggedArray};
+use crate::vec_like::VecLike;
+
+impl<TVal, TBuffer: VecLike, const N: usize> JaggedArray<TVal, TBuffer, N>
+where
+    TBuffer::TI: AsPrimitive<usize> + Num + ConstZero,
+    usize: AsPrimitive<TBuffer::TI>,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength + NonZero,
+    Const<N>: ToUInt,
+{
+    /// Creates an array from a dense array whose rows of dimension `N - 1` are padded,
+    /// keeping the first `lengths[index]` values of the row at `index`.
+    /// The rows of the other dimensions are all kept.
+    ///
+    /// Returns [`JaggedArrayError::InvalidFormat`] if the shape of `lengths` differs
+    /// from the shape of the rows of `array`, [`JaggedArrayError::OutOfBounds`] if a length
+    /// exceeds the length of its row and [`JaggedArrayError::IndexOverflow`]
+    /// if the offsets do not fit in the index type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jaggedarray::{jagged, JaggedArray};
+    /// let array = ndarray::array![[1, 2, 0], [3, 0, 0], [0, 0, 0]];
+    /// let lengths = ndarray::array![2, 1, 0];
+    /// let data = JaggedArray::<i32, Vec<u16>, 2>::from_array(array.view(), lengths.view());
+    /// assert!(data.unwrap() == jagged![u16; [1, 2], [3], []]);
+    /// ```
+    pub fn from_array(
+        array: ArrayView<TVal, Dim<[usize; N]>>,
+        lengths: ArrayView<usize, <Dim<[usize; N]> as Dimension>::Smaller>,
+    ) -> Result<Self, JaggedArrayError>
+    where
+        TVal: Clone,
+        Dim<[usize; N]>: Dimension,
+    {
+        let shape = array.shape();
+        if lengths.shape() != &shape[..N - 1] {
+            return Err(JaggedArrayError::InvalidFormat("lengths shape mismatch"));
+        }
+        let mut rows = 1;
+        let mut indices = Vec::with_capacity(N - 1);
+        for pair in shape[..N - 1].windows(2) {
+            rows *= pair[0];
+            indices.push(
+                (0..=rows)
+                    .map(|row| to_index(row * pair[1]))
+                    .collect::<Result<TBuffer, _>>()?,
+            );
+        }
+        let mut offsets = TBuffer::default();
+        offsets.try_reserve(lengths.len() + 1)?;
+        offsets.push(TBuffer::TI::ZERO);
+        let mut buffer = Vec::new();
+        for (row, &len) in array.rows().into_iter().zip(lengths.iter()) {
+            if len > row.len() {
+                return Err(JaggedArrayError::OutOfBounds);
+            }
+            buffer.extend(row.iter().take(len).cloned());
+            offsets.push(to_index(buffer.len())?);
+        }
+        indices.push(offsets);
+        Ok(JaggedArray {
+            indices: indices.into_iter().collect(),
+            buffer,
+        })
+    }
+}
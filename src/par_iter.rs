@@ -0,0 +1,222 @@
+//! Parallel iterators over jagged arrays, available with the `rayon` feature.
+use generic_array::ArrayLength;
+use num::traits::AsPrimitive;
+use num::Num;
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use typenum::{Const, ToUInt, B1, U};
+
+use crate::iter::{Rows, RowsMut};
+use crate::jagged_array::{JaggedArrayMutView, JaggedArrayView};
+
+/// A parallel iterator over the outermost rows of a jagged array or view.
+///
+/// The rows are split into contiguous ranges, so every task works on a contiguous part of the buffer.
+/// This struct is created by [`par_rows`](crate::JaggedArrayViewTrait::par_rows).
+#[derive(Debug, Clone)]
+pub struct ParRows<'a, TVal, TNum, const R: usize>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    rows: Rows<'a, TVal, TNum, R>,
+}
+
+impl<'a, TVal, TNum, const R: usize> ParRows<'a, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    #[inline]
+    pub(crate) fn new(rows: Rows<'a, TVal, TNum, R>) -> Self {
+        Self { rows }
+    }
+}
+
+struct RowsProducer<'a, TVal, TNum, const R: usize>(Rows<'a, TVal, TNum, R>)
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt;
+
+impl<'a, TVal, TNum, const R: usize> Producer for RowsProducer<'a, TVal, TNum, R>
+where
+    TVal: Sync,
+    TNum: AsPrimitive<usize> + Num + Sync,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    type Item = JaggedArrayView<'a, TVal, TNum, R>;
+    type IntoIter = Rows<'a, TVal, TNum, R>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0
+    }
+
+    #[inline]
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.0.split_at(index);
+        (Self(left), Self(right))
+    }
+}
+
+impl<'a, TVal, TNum, const R: usize> ParallelIterator for ParRows<'a, TVal, TNum, R>
+where
+    TVal: Sync,
+    TNum: AsPrimitive<usize> + Num + Sync,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    type Item = JaggedArrayView<'a, TVal, TNum, R>;
+
+    #[inline]
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.rows.len())
+    }
+}
+
+impl<TVal, TNum, const R: usize> IndexedParallelIterator for ParRows<'_, TVal, TNum, R>
+where
+    TVal: Sync,
+    TNum: AsPrimitive<usize> + Num + Sync,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[inline]
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(RowsProducer(self.rows))
+    }
+}
+
+/// A parallel iterator over the outermost rows of a jagged array or view that yields disjoint mutable views.
+///
+/// The rows are split into contiguous ranges, so every task works on a contiguous part of the buffer.
+/// This struct is created by [`par_rows_mut`](crate::JaggedArrayMutViewTrait::par_rows_mut).
+#[derive(Debug)]
+pub struct ParRowsMut<'a, TVal, TNum, const R: usize>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    rows: RowsMut<'a, TVal, TNum, R>,
+}
+
+impl<'a, TVal, TNum, const R: usize> ParRowsMut<'a, TVal, TNum, R>
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    #[inline]
+    pub(crate) fn new(rows: RowsMut<'a, TVal, TNum, R>) -> Self {
+        Self { rows }
+    }
+}
+
+struct RowsMutProducer<'a, TVal, TNum, const R: usize>(RowsMut<'a, TVal, TNum, R>)
+where
+    TNum: AsPrimitive<usize> + Num,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt;
+
+impl<'a, TVal, TNum, const R: usize> Producer for RowsMutProducer<'a, TVal, TNum, R>
+where
+    TVal: Send,
+    TNum: AsPrimitive<usize> + Num + Sync,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    type Item = JaggedArrayMutView<'a, TVal, TNum, R>;
+    type IntoIter = RowsMut<'a, TVal, TNum, R>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0
+    }
+
+    #[inline]
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.0.split_at(index);
+        (Self(left), Self(right))
+    }
+}
+
+impl<'a, TVal, TNum, const R: usize> ParallelIterator for ParRowsMut<'a, TVal, TNum, R>
+where
+    TVal: Send,
+    TNum: AsPrimitive<usize> + Num + Sync,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    type Item = JaggedArrayMutView<'a, TVal, TNum, R>;
+
+    #[inline]
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.rows.len())
+    }
+}
+
+impl<TVal, TNum, const R: usize> IndexedParallelIterator for ParRowsMut<'_, TVal, TNum, R>
+where
+    TVal: Send,
+    TNum: AsPrimitive<usize> + Num + Sync,
+    U<R>: std::ops::Sub<B1>,
+    <U<R> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<R>: ToUInt,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[inline]
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(RowsMutProducer(self.rows))
+    }
+}
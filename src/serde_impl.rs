@@ -0,0 +1,428 @@
+//! Human-readable formats store arrays as nested sequences,
+//! other formats store them as a struct of the offsets of every level and the flat buffer.
+use std::fmt::{self, Formatter};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use generic_array::{ArrayLength, GenericArray};
+use num::traits::{AsPrimitive, CheckedAdd, ConstOne, ConstZero, NumAssignOps};
+use num::Num;
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+use typenum::{Const, Sub1, ToUInt, B1, U};
+
+use crate::jagged_array::{
+    to_index, JaggedArray, JaggedArrayMutView, JaggedArrayOwnedView, JaggedArrayView,
+};
+use crate::vec_like::VecLike;
+
+const FIELDS: &[&str] = &["indices", "buffer"];
+
+/// The rows in `rows` of the outermost level, serialized as nested sequences.
+struct Nested<'a, TVal, TNum> {
+    levels: &'a [&'a [TNum]],
+    buffer: &'a [TVal],
+    rows: Range<usize>,
+}
+
+impl<TVal, TNum> Serialize for Nested<'_, TVal, TNum>
+where
+    TVal: Serialize,
+    TNum: AsPrimitive<usize>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.levels.split_first() {
+            Some((level, levels)) => {
+                let base = level[0].as_();
+                serializer.collect_seq(self.rows.clone().map(|i| Nested {
+                    levels,
+                    buffer: self.buffer,
+                    rows: level[i].as_() - base..level[i + 1].as_() - base,
+                }))
+            }
+            None => serializer.collect_seq(&self.buffer[self.rows.clone()]),
+        }
+    }
+}
+
+/// A level of offsets serialized so that it starts from zero.
+struct Rebased<'a, TNum>(&'a [TNum]);
+
+impl<TNum> Serialize for Rebased<'_, TNum>
+where
+    TNum: Serialize + Num + Copy,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|&x| x - self.0[0]))
+    }
+}
+
+/// Every level of offsets, each serialized so that it starts from zero.
+struct Levels<'a, TNum>(&'a [&'a [TNum]]);
+
+impl<TNum> Serialize for Levels<'_, TNum>
+where
+    TNum: Serialize + Num + Copy,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|level| Rebased(level)))
+    }
+}
+
+fn serialize_levels<S, TVal, TNum>(
+    levels: &[&[TNum]],
+    buffer: &[TVal],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    TVal: Serialize,
+    TNum: Serialize + AsPrimitive<usize> + Num,
+{
+    if serializer.is_human_readable() {
+        let rows = match levels.first() {
+            Some(level) => 0..level.len() - 1,
+            None => 0..buffer.len(),
+        };
+        Nested {
+            levels,
+            buffer,
+            rows,
+        }
+        .serialize(serializer)
+    } else {
+        let mut state = serializer.serialize_struct("JaggedArray", FIELDS.len())?;
+        state.serialize_field("indices", &Levels(levels))?;
+        state.serialize_field("buffer", buffer)?;
+        state.end()
+    }
+}
+
+macro_rules! impl_serialize {
+    ($num:ty, $typ:ident< $( $gen:tt ),+>,$type1:ty,$type2:path) => {
+        impl<$( $gen ),+, const N: usize> Serialize for $typ<$($gen),+, N>
+        where
+            TVal: Serialize,
+            $num: Serialize + AsPrimitive<usize> + Num,
+            $type1: $type2,
+            U<N>: std::ops::Sub<B1>,
+            <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+            Const<N>: ToUInt,
+        {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let levels = self.indices.iter().map(|index| &index[..]).collect::<Vec<_>>();
+                serialize_levels(&levels, &self.buffer, serializer)
+            }
+        }
+    };
+}
+
+impl_serialize!(<TBuffer as VecLike>::TI, JaggedArray<TVal, TBuffer>, TBuffer, VecLike);
+impl_serialize!(TNum, JaggedArrayView<'a, TVal, TNum>, TNum, Num);
+impl_serialize!(TNum, JaggedArrayMutView<'a, TVal, TNum>, TNum, Num);
+impl_serialize!(TNum, JaggedArrayOwnedView<TVal, TNum>, TNum, Num);
+
+/// Appends a nested sequence to `array`,
+/// whose elements are rows of dimension `depth + 1`, or values if `depth` is `N - 1`.
+struct NestedSeed<'a, TVal, TBuffer: VecLike, const N: usize>
+where
+    TBuffer::TI: AsPrimitive<usize> + Num,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    array: &'a mut JaggedArray<TVal, TBuffer, N>,
+    depth: usize,
+}
+
+impl<'de, TVal, TBuffer: VecLike, const N: usize> DeserializeSeed<'de>
+    for NestedSeed<'_, TVal, TBuffer, N>
+where
+    TVal: Deserialize<'de>,
+    TBuffer::TI: AsPrimitive<usize> + Num,
+    usize: AsPrimitive<TBuffer::TI>,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, TVal, TBuffer: VecLike, const N: usize> Visitor<'de> for NestedSeed<'_, TVal, TBuffer, N>
+where
+    TVal: Deserialize<'de>,
+    TBuffer::TI: AsPrimitive<usize> + Num,
+    usize: AsPrimitive<TBuffer::TI>,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{} levels of nested sequences", N - self.depth)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        if self.depth + 1 == N {
+            while let Some(value) = seq.next_element()? {
+                self.array.buffer.push(value);
+            }
+            return Ok(());
+        }
+        while seq
+            .next_element_seed(NestedSeed {
+                array: &mut *self.array,
+                depth: self.depth + 1,
+            })?
+            .is_some()
+        {
+            let len = match self.array.indices.get(self.depth + 1) {
+                Some(index) => index.len() - 1,
+                None => self.array.buffer.len(),
+            };
+            let offset = to_index(len).map_err(de::Error::custom)?;
+            self.array.indices[self.depth]
+                .try_push(offset)
+                .map_err(de::Error::custom)?;
+        }
+        Ok(())
+    }
+}
+
+/// Deserializes a single level of offsets.
+struct LevelVisitor<TBuffer>(PhantomData<TBuffer>);
+
+impl<'de, TBuffer: VecLike> Visitor<'de> for LevelVisitor<TBuffer>
+where
+    TBuffer::TI: Deserialize<'de>,
+{
+    type Value = TBuffer;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of offsets")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<TBuffer, A::Error> {
+        let mut level = TBuffer::default();
+        while let Some(offset) = seq.next_element()? {
+            level.try_push(offset).map_err(de::Error::custom)?;
+        }
+        Ok(level)
+    }
+}
+
+impl<'de, TBuffer: VecLike> DeserializeSeed<'de> for LevelVisitor<TBuffer>
+where
+    TBuffer::TI: Deserialize<'de>,
+{
+    type Value = TBuffer;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<TBuffer, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+/// Deserializes exactly `N - 1` levels of offsets.
+struct LevelsVisitor<TBuffer, const N: usize>(PhantomData<TBuffer>);
+
+impl<'de, TBuffer: VecLike, const N: usize> Visitor<'de> for LevelsVisitor<TBuffer, N>
+where
+    TBuffer::TI: Deserialize<'de>,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Value = GenericArray<TBuffer, Sub1<U<N>>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{} levels of offsets", N - 1)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut levels = Vec::with_capacity(N - 1);
+        for i in 0..N - 1 {
+            let level = seq.next_element_seed(LevelVisitor(PhantomData))?;
+            levels.push(level.ok_or_else(|| de::Error::invalid_length(i, &self))?);
+        }
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(N, &self));
+        }
+        Ok(levels.into_iter().collect())
+    }
+}
+
+impl<'de, TBuffer: VecLike, const N: usize> DeserializeSeed<'de> for LevelsVisitor<TBuffer, N>
+where
+    TBuffer::TI: Deserialize<'de>,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Value = GenericArray<TBuffer, Sub1<U<N>>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+enum Field {
+    Indices,
+    Buffer,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        impl Visitor<'_> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("`indices` or `buffer`")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Field, E> {
+                match value {
+                    0 => Ok(Field::Indices),
+                    1 => Ok(Field::Buffer),
+                    _ => Err(de::Error::invalid_value(
+                        de::Unexpected::Unsigned(value),
+                        &self,
+                    )),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+                match value {
+                    "indices" => Ok(Field::Indices),
+                    "buffer" => Ok(Field::Buffer),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+/// Deserializes the offsets and the buffer of an array and validates them.
+struct CompactVisitor<TVal, TBuffer, const N: usize>(PhantomData<(TVal, TBuffer)>);
+
+impl<'de, TVal, TBuffer: VecLike, const N: usize> Visitor<'de> for CompactVisitor<TVal, TBuffer, N>
+where
+    TVal: Deserialize<'de>,
+    TBuffer::TI: Deserialize<'de>
+        + AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: AsPrimitive<TBuffer::TI>,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    type Value = JaggedArray<TVal, TBuffer, N>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("struct JaggedArray")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let indices = seq
+            .next_element_seed(LevelsVisitor(PhantomData))?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let buffer = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        JaggedArray::from_raw_parts(indices, buffer).map_err(de::Error::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut indices = None;
+        let mut buffer = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Indices if indices.is_some() => {
+                    return Err(de::Error::duplicate_field("indices"))
+                }
+                Field::Indices => indices = Some(map.next_value_seed(LevelsVisitor(PhantomData))?),
+                Field::Buffer if buffer.is_some() => {
+                    return Err(de::Error::duplicate_field("buffer"))
+                }
+                Field::Buffer => buffer = Some(map.next_value()?),
+            }
+        }
+        let indices = indices.ok_or_else(|| de::Error::missing_field("indices"))?;
+        let buffer = buffer.ok_or_else(|| de::Error::missing_field("buffer"))?;
+        JaggedArray::from_raw_parts(indices, buffer).map_err(de::Error::custom)
+    }
+}
+
+impl<'de, TVal, TBuffer: VecLike, const N: usize> Deserialize<'de> for JaggedArray<TVal, TBuffer, N>
+where
+    TVal: Deserialize<'de>,
+    TBuffer::TI: Deserialize<'de>
+        + AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: AsPrimitive<TBuffer::TI>,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let mut array = JaggedArray::new();
+            NestedSeed {
+                array: &mut array,
+                depth: 0,
+            }
+            .deserialize(deserializer)?;
+            Ok(array)
+        } else {
+            deserializer.deserialize_struct("JaggedArray", FIELDS, CompactVisitor(PhantomData))
+        }
+    }
+}
+
+impl<'de, TVal, TNum, const N: usize> Deserialize<'de> for JaggedArrayOwnedView<TVal, TNum, N>
+where
+    TVal: Deserialize<'de>,
+    TNum: Deserialize<'de>
+        + AsPrimitive<usize>
+        + Num
+        + NumAssignOps
+        + CheckedAdd
+        + PartialOrd
+        + ConstOne
+        + ConstZero,
+    usize: AsPrimitive<TNum>,
+    U<N>: std::ops::Sub<B1>,
+    <U<N> as std::ops::Sub<B1>>::Output: ArrayLength,
+    Const<N>: ToUInt,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let array = JaggedArray::<TVal, Vec<TNum>, N>::deserialize(deserializer)?;
+        Ok(JaggedArrayOwnedView {
+            indices: array
+                .indices
+                .into_iter()
+                .map(Vec::into_boxed_slice)
+                .collect(),
+            buffer: array.buffer.into_boxed_slice(),
+        })
+    }
+}
@@ -1,6 +1,8 @@
 use std::ops::{Deref, DerefMut, Index, IndexMut, Range, RangeBounds, RangeFrom, RangeFull};
 
 use tinyvec::{Array, ArrayVec};
+
+use crate::error::JaggedArrayError;
 pub trait VecLike:
     Deref<Target = [Self::TI]>
     + DerefMut
@@ -25,10 +27,27 @@ pub trait VecLike:
         self.len() == 0
     }
     fn reserve(&mut self, additional: usize);
+    /// Same as [`VecLike::reserve`], but returns an error instead of panicking or aborting
+    /// if the capacity cannot be reserved.
+    fn try_reserve(&mut self, additional: usize) -> Result<(), JaggedArrayError>;
     fn push(&mut self, item: Self::Item);
+    /// Same as [`VecLike::push`], but returns an error instead of panicking or aborting
+    /// if the item cannot be stored.
+    fn try_push(&mut self, item: Self::TI) -> Result<(), JaggedArrayError>;
     fn pop(&mut self) -> Option<Self::Item>;
     fn remove(&mut self, index: usize) -> Self::Item;
     fn insert(&mut self, index: usize, item: Self::Item);
+    /// Inserts the items of `iter` at `index`, shifting the following items to the right.
+    ///
+    /// The default implementation inserts the items one by one.
+    fn insert_iter<I>(&mut self, index: usize, iter: I)
+    where
+        I: IntoIterator<Item = Self::TI>,
+    {
+        for (offset, item) in iter.into_iter().enumerate() {
+            self.insert(index + offset, item);
+        }
+    }
     fn clear(&mut self);
     fn truncate(&mut self, len: usize);
     fn remove_range<R>(&mut self, range: R)
@@ -47,6 +66,12 @@ impl<T> VecLike for Vec<T> {
         Vec::push(self, item)
     }
     #[inline]
+    fn try_push(&mut self, item: T) -> Result<(), JaggedArrayError> {
+        Vec::try_reserve(self, 1)?;
+        Vec::push(self, item);
+        Ok(())
+    }
+    #[inline]
     fn pop(&mut self) -> Option<T> {
         Vec::pop(self)
     }
@@ -59,6 +84,13 @@ impl<T> VecLike for Vec<T> {
         Vec::insert(self, index, item)
     }
     #[inline]
+    fn insert_iter<I>(&mut self, index: usize, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Vec::splice(self, index..index, iter);
+    }
+    #[inline]
     fn clear(&mut self) {
         Vec::clear(self)
     }
@@ -67,6 +99,10 @@ impl<T> VecLike for Vec<T> {
         Vec::reserve(self, additional)
     }
     #[inline]
+    fn try_reserve(&mut self, additional: usize) -> Result<(), JaggedArrayError> {
+        Ok(Vec::try_reserve(self, additional)?)
+    }
+    #[inline]
     fn truncate(&mut self, len: usize) {
         Vec::truncate(self, len)
     }
@@ -90,6 +126,13 @@ impl<A: Array + Clone> VecLike for ArrayVec<A> {
         ArrayVec::push(self, item)
     }
     #[inline]
+    fn try_push(&mut self, item: A::Item) -> Result<(), JaggedArrayError> {
+        match ArrayVec::try_push(self, item) {
+            Some(_) => Err(JaggedArrayError::CapacityExhausted),
+            None => Ok(()),
+        }
+    }
+    #[inline]
     fn pop(&mut self) -> Option<A::Item> {
         ArrayVec::pop(self)
     }
@@ -102,12 +145,27 @@ impl<A: Array + Clone> VecLike for ArrayVec<A> {
         ArrayVec::insert(self, index, item)
     }
     #[inline]
+    fn insert_iter<I>(&mut self, index: usize, iter: I)
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        ArrayVec::splice(self, index..index, iter);
+    }
+    #[inline]
     fn clear(&mut self) {
         ArrayVec::clear(self)
     }
     #[inline]
     fn reserve(&mut self, _: usize) {}
     #[inline]
+    fn try_reserve(&mut self, additional: usize) -> Result<(), JaggedArrayError> {
+        if ArrayVec::capacity(self) - ArrayVec::len(self) < additional {
+            Err(JaggedArrayError::CapacityExhausted)
+        } else {
+            Ok(())
+        }
+    }
+    #[inline]
     fn truncate(&mut self, len: usize) {
         ArrayVec::truncate(self, len)
     }